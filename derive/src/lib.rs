@@ -0,0 +1,310 @@
+//! The `#[human_bytes]` attribute of
+//! [`serde_human_bytes`](https://docs.rs/serde-human-bytes), re-exported from
+//! there with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields,
+    GenericArgument, LitStr, PathArguments, Result, Token, Type,
+};
+
+/// Apply `serde_human_bytes` to the byte fields of a struct or enum.
+///
+/// Placed above `#[derive(Serialize, Deserialize)]`, it adds
+/// `#[serde(with = "serde_human_bytes")]` to every field of type `Vec<u8>`,
+/// `[u8; N]`, `&[u8]`, `Box<[u8]>` or `Cow<[u8]>`, or an `Option` of one of
+/// those, in a struct or in the variants of an enum.
+///
+/// - `#[human_bytes(base64)]` on the type uses `serde_human_bytes::base64`
+///   instead.
+/// - `#[human_bytes(hex)]` or `#[human_bytes(base64)]` on a field picks its
+///   encoding, and also applies to field types not listed above, such as
+///   `ByteBuf` or `ByteArray<N>`.
+/// - `#[human_bytes(skip)]` on a field leaves it alone.
+///
+/// Fields that already have a serde `with`, `serialize_with` or
+/// `deserialize_with` attribute are left alone too, and giving one of them a
+/// `#[human_bytes(hex)]` or `#[human_bytes(base64)]` is an error.
+///
+/// ```ignore
+/// use serde::{Deserialize, Serialize};
+/// use serde_human_bytes::human_bytes;
+///
+/// #[human_bytes(base64)]
+/// #[derive(Deserialize, Serialize)]
+/// struct Envelope<'a> {
+///     key: [u8; 32],
+///     payload: Cow<'a, [u8]>,
+///
+///     #[human_bytes(hex)]
+///     digest: Option<Vec<u8>>,
+///
+///     #[human_bytes(skip)]
+///     flags: Vec<u8>,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn human_bytes(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let encoding = match parse_encoding(args.into()) {
+        Ok(encoding) => encoding,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match expand(&mut input, encoding) {
+        Ok(()) => quote!(#input).into(),
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote!(#input #err).into()
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Encoding {
+    Hex,
+    Base64,
+}
+
+impl Encoding {
+    fn module(self) -> &'static str {
+        match self {
+            Encoding::Hex => "serde_human_bytes",
+            Encoding::Base64 => "serde_human_bytes::base64",
+        }
+    }
+}
+
+/// What a field's own `#[human_bytes(...)]` asks for.
+enum FieldChoice {
+    Default,
+    Skip,
+    /// An encoding, and the tokens of the attribute naming it.
+    Encoding(Encoding, TokenStream2),
+}
+
+fn parse_encoding(args: proc_macro2::TokenStream) -> Result<Encoding> {
+    let mut encoding = Encoding::Hex;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("hex") {
+            encoding = Encoding::Hex;
+        } else if meta.path.is_ident("base64") {
+            encoding = Encoding::Base64;
+        } else {
+            return Err(meta.error("expected `hex` or `base64`"));
+        }
+        Ok(())
+    });
+    syn::parse::Parser::parse2(parser, args)?;
+    Ok(encoding)
+}
+
+fn expand(input: &mut DeriveInput, encoding: Encoding) -> Result<()> {
+    match &mut input.data {
+        Data::Struct(data) => expand_fields(&mut data.fields, encoding),
+        Data::Enum(data) => data
+            .variants
+            .iter_mut()
+            .try_for_each(|variant| expand_fields(&mut variant.fields, encoding)),
+        Data::Union(_) => Err(Error::new(
+            Span::call_site(),
+            "#[human_bytes] is not supported on unions",
+        )),
+    }
+}
+
+fn expand_fields(fields: &mut Fields, encoding: Encoding) -> Result<()> {
+    let mut errors: Option<Error> = None;
+    for field in fields.iter_mut() {
+        if let Err(err) = expand_field(field, encoding) {
+            match &mut errors {
+                Some(errors) => errors.combine(err),
+                None => errors = Some(err),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+fn expand_field(field: &mut Field, encoding: Encoding) -> Result<()> {
+    let choice = take_field_choice(&mut field.attrs)?;
+    if has_serde_key(
+        &field.attrs,
+        &["with", "serialize_with", "deserialize_with"],
+    ) {
+        if let FieldChoice::Encoding(_, attr) = choice {
+            return Err(Error::new_spanned(
+                attr,
+                "#[human_bytes] encoding conflicts with the field's own serde `with`, `serialize_with` or `deserialize_with`",
+            ));
+        }
+        return Ok(());
+    }
+    let (encoding, kind) = match choice {
+        FieldChoice::Skip => return Ok(()),
+        FieldChoice::Encoding(encoding, _) => (encoding, classify(&field.ty)),
+        FieldChoice::Default => match classify(&field.ty) {
+            Some(kind) => (encoding, Some(kind)),
+            None => return Ok(()),
+        },
+    };
+    let module = LitStr::new(encoding.module(), Span::call_site());
+    field.attrs.push(parse_quote!(#[serde(with = #module)]));
+    if let Some(Kind { borrow: true }) = kind {
+        if has_serde_key(&field.attrs, &["borrow"]) {
+            return Ok(());
+        }
+        field.attrs.push(parse_quote!(#[serde(borrow)]));
+    }
+    Ok(())
+}
+
+/// Remove the `#[human_bytes(...)]` attributes of a field, which are only
+/// meaningful to this macro.
+fn take_field_choice(attrs: &mut Vec<Attribute>) -> Result<FieldChoice> {
+    let mut choice = FieldChoice::Default;
+    let mut result = Ok(());
+    attrs.retain(|attr| {
+        if !attr.path().is_ident("human_bytes") {
+            return true;
+        }
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                choice = FieldChoice::Skip;
+            } else if meta.path.is_ident("hex") {
+                choice = FieldChoice::Encoding(Encoding::Hex, attr.to_token_stream());
+            } else if meta.path.is_ident("base64") {
+                choice = FieldChoice::Encoding(Encoding::Base64, attr.to_token_stream());
+            } else {
+                return Err(meta.error("expected `skip`, `hex` or `base64`"));
+            }
+            Ok(())
+        });
+        if let Err(err) = parsed {
+            result = Err(err);
+        }
+        false
+    });
+    result.map(|()| choice)
+}
+
+/// Whether the field's own `#[serde(...)]` attributes use any of `keys`, such
+/// as `with` or `borrow`.
+fn has_serde_key(attrs: &[Attribute], keys: &[&str]) -> bool {
+    let mut found = false;
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        // Serde reports anything malformed in here; only look for the keys.
+        let _ = attr.parse_nested_meta(|meta| {
+            if keys.iter().any(|key| meta.path.is_ident(key)) {
+                found = true;
+            }
+            if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.input.peek(Token![=]) {
+                        nested.value()?.parse::<syn::Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// A recognized byte field type.
+struct Kind {
+    /// Whether the type holds a lifetime that serde does not borrow on its
+    /// own. Plain `&[u8]` is always borrowed, `Cow<[u8]>` and `Option`s of
+    /// references are not unless asked to.
+    borrow: bool,
+}
+
+fn classify(ty: &Type) -> Option<Kind> {
+    if let Some(inner) = generic_arg(ty, "Option") {
+        return classify_bytes(inner).map(|borrow| Kind {
+            borrow: borrow || is_ref(inner),
+        });
+    }
+    classify_bytes(ty).map(|borrow| Kind { borrow })
+}
+
+/// `Some(borrow)` for the byte types themselves, `borrow` being whether serde
+/// needs to be told to borrow it.
+fn classify_bytes(ty: &Type) -> Option<bool> {
+    match ungroup(ty) {
+        Type::Array(array) if is_u8(&array.elem) => Some(false),
+        Type::Reference(reference)
+            if reference.mutability.is_none() && is_u8_slice(&reference.elem) =>
+        {
+            Some(false)
+        }
+        _ => {
+            if generic_arg(ty, "Vec").is_some_and(is_u8)
+                || generic_arg(ty, "Box").is_some_and(is_u8_slice)
+            {
+                Some(false)
+            } else if generic_arg(ty, "Cow").is_some_and(is_u8_slice) {
+                Some(true)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn is_ref(ty: &Type) -> bool {
+    matches!(ungroup(ty), Type::Reference(_))
+}
+
+fn is_u8(ty: &Type) -> bool {
+    matches!(ungroup(ty), Type::Path(path) if path.qself.is_none() && path.path.is_ident("u8"))
+}
+
+fn is_u8_slice(ty: &Type) -> bool {
+    matches!(ungroup(ty), Type::Slice(slice) if is_u8(&slice.elem))
+}
+
+/// The only type argument of `ty` if its last path segment is `name`, as in
+/// `Vec<u8>`, `std::borrow::Cow<'a, [u8]>` or `Option<T>`.
+fn generic_arg<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let path = match ungroup(ty) {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let args: &Punctuated<GenericArgument, Token![,]> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+    let mut types = args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next()) {
+        (Some(ty), None) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Look through the invisible groups and parentheses that `macro_rules!`
+/// leaves around substituted types.
+fn ungroup(mut ty: &Type) -> &Type {
+    loop {
+        match ty {
+            Type::Group(group) => ty = &group.elem,
+            Type::Paren(paren) => ty = &paren.elem,
+            _ => return ty,
+        }
+    }
+}
@@ -0,0 +1,834 @@
+//! Decoding borrowed byte fields into a [`bumpalo`] arena.
+//!
+//! Compact formats can hand out `&'de [u8]` pointing into their input, but
+//! hex or base64 has to be decoded somewhere first. Fields using
+//! `#[serde(with = "serde_human_bytes::arena")]` (hex) or
+//! `#[serde(with = "serde_human_bytes::arena::base64")]` borrow from the
+//! input of compact formats, and from the arena of an [`Arena`] deserializer
+//! wrapped around human-readable ones. The same borrowed struct then serves
+//! both:
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use bumpalo::Bump;
+//! use serde::{Deserialize, Serialize};
+//! use serde_human_bytes::arena::Arena;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Record<'a> {
+//!     #[serde(with = "serde_human_bytes::arena", borrow)]
+//!     key: &'a [u8],
+//!
+//!     #[serde(with = "serde_human_bytes::arena::base64", borrow)]
+//!     value: Option<&'a [u8]>,
+//! }
+//!
+//! let bytes = bincode::serialize(&Record { key: b"k", value: None })?;
+//! let record: Record = bincode::deserialize(&bytes)?;
+//! assert_eq!(record.key, b"k");
+//!
+//! let json = r#"{"key":"6b","value":"dg=="}"#;
+//! let bump = Bump::new();
+//! let mut deserializer = serde_json::Deserializer::from_str(json);
+//! let record = Record::deserialize(Arena::new(&mut deserializer, &bump))?;
+//! assert_eq!(record.value, Some(&b"v"[..]));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Deserializing these fields from a human-readable format without an
+//! `Arena` is an error.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use bumpalo::Bump;
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::Serializer;
+
+use crate::dynamic::Encoding;
+use crate::seed;
+use crate::Bytes;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Newtype struct names by which the fields ask an `Arena` for decoded bytes.
+const HEX: &str = "$serde_human_bytes::arena::hex";
+const BASE64: &str = "$serde_human_bytes::arena::base64";
+
+/// A deserializer, or a seed, that decodes human-readable byte fields into
+/// an arena.
+///
+/// Wrap the format's deserializer with [`Arena::new`] and deserialize from
+/// that. Everything else is deserialized exactly as by the inner
+/// deserializer.
+pub struct Arena<'de, T> {
+    inner: T,
+    bump: &'de Bump,
+}
+
+impl<'de, T> Arena<'de, T> {
+    /// Decode the byte fields reached through `inner` into `bump`.
+    pub fn new(inner: T, bump: &'de Bump) -> Self {
+        Arena { inner, bump }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for Arena<'de, T>
+where
+    T: DeserializeSeed<'de>,
+{
+    type Value = T::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner.deserialize(Arena {
+            inner: deserializer,
+            bump: self.bump,
+        })
+    }
+}
+
+/// Decodes a string into the arena and hands the field borrowed bytes.
+struct DecodeVisitor<'de, V> {
+    visitor: V,
+    encoding: Encoding,
+    bump: &'de Bump,
+}
+
+impl<'de, V> Visitor<'de> for DecodeVisitor<'de, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a {} string", self.encoding)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<V::Value, E>
+    where
+        E: Error,
+    {
+        let out = self
+            .bump
+            .alloc_slice_fill_copy(seed::decoded_len(v, self.encoding), 0);
+        match seed::decode(v, self.encoding, out) {
+            Ok(len) => self.visitor.visit_borrowed_bytes(&out[..len]),
+            Err(err) => Err(err.into_de_error(&self)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<V::Value, E>
+    where
+        E: Error,
+    {
+        match core::str::from_utf8(v) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+macro_rules! forward_deserialize_methods {
+    ( ( $( $name: ident ),* ) ) => {
+        $(
+            fn $name<V>(self, visitor: V) -> Result<V::Value, D::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.inner.$name(Arena {
+                    inner: visitor,
+                    bump: self.bump,
+                })
+            }
+        )*
+    };
+}
+
+impl<'de, D> Deserializer<'de> for Arena<'de, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize_methods! {
+        (
+            deserialize_any,
+            deserialize_bool,
+            deserialize_u8,
+            deserialize_u16,
+            deserialize_u32,
+            deserialize_u64,
+            deserialize_u128,
+            deserialize_i8,
+            deserialize_i16,
+            deserialize_i32,
+            deserialize_i64,
+            deserialize_i128,
+            deserialize_f32,
+            deserialize_f64,
+            deserialize_char,
+            deserialize_str,
+            deserialize_string,
+            deserialize_bytes,
+            deserialize_byte_buf,
+            deserialize_option,
+            deserialize_unit,
+            deserialize_seq,
+            deserialize_map,
+            deserialize_identifier,
+            deserialize_ignored_any
+        )
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.deserialize_unit_struct(
+            name,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let encoding = match name {
+            HEX => Encoding::Hex,
+            BASE64 => Encoding::Base64,
+            _ => {
+                let visitor = Arena {
+                    inner: visitor,
+                    bump: self.bump,
+                };
+                return self.inner.deserialize_newtype_struct(name, visitor);
+            }
+        };
+        self.inner.deserialize_str(DecodeVisitor {
+            visitor,
+            encoding,
+            bump: self.bump,
+        })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.deserialize_tuple(
+            len,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.deserialize_tuple_struct(
+            name,
+            len,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.deserialize_struct(
+            name,
+            fields,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.deserialize_enum(
+            name,
+            variants,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<'de, D> Visitor<'de> for Arena<'de, D>
+where
+    D: Visitor<'de>,
+{
+    type Value = D::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_bool(v)
+    }
+
+    fn visit_i8<E>(self, v: i8) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_i8(v)
+    }
+
+    fn visit_i16<E>(self, v: i16) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_i16(v)
+    }
+
+    fn visit_i32<E>(self, v: i32) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_i32(v)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_i64(v)
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_i128(v)
+    }
+
+    fn visit_u8<E>(self, v: u8) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_u8(v)
+    }
+
+    fn visit_u16<E>(self, v: u16) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_u16(v)
+    }
+
+    fn visit_u32<E>(self, v: u32) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_u32(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_u64(v)
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_u128(v)
+    }
+
+    fn visit_f32<E>(self, v: f32) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_f32(v)
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_f64(v)
+    }
+
+    fn visit_char<E>(self, v: char) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_char(v)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_borrowed_str(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_string<E>(self, v: String) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_string(v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_borrowed_bytes(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_byte_buf(v)
+    }
+
+    fn visit_none<E>(self) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D2>(self, deserializer: D2) -> Result<Self::Value, D2::Error>
+    where
+        D2: Deserializer<'de>,
+    {
+        self.inner.visit_some(Arena {
+            inner: deserializer,
+            bump: self.bump,
+        })
+    }
+
+    fn visit_unit<E>(self) -> Result<D::Value, E>
+    where
+        E: Error,
+    {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D2>(self, deserializer: D2) -> Result<Self::Value, D2::Error>
+    where
+        D2: Deserializer<'de>,
+    {
+        self.inner.visit_newtype_struct(Arena {
+            inner: deserializer,
+            bump: self.bump,
+        })
+    }
+
+    fn visit_seq<V>(self, seq: V) -> Result<D::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        self.inner.visit_seq(Arena {
+            inner: seq,
+            bump: self.bump,
+        })
+    }
+
+    fn visit_map<V>(self, map: V) -> Result<D::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        self.inner.visit_map(Arena {
+            inner: map,
+            bump: self.bump,
+        })
+    }
+
+    fn visit_enum<V>(self, data: V) -> Result<D::Value, V::Error>
+    where
+        V: EnumAccess<'de>,
+    {
+        self.inner.visit_enum(Arena {
+            inner: data,
+            bump: self.bump,
+        })
+    }
+}
+
+impl<'de, D> SeqAccess<'de> for Arena<'de, D>
+where
+    D: SeqAccess<'de>,
+{
+    type Error = D::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, D::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.inner.next_element_seed(Arena {
+            inner: seed,
+            bump: self.bump,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, D> MapAccess<'de> for Arena<'de, D>
+where
+    D: MapAccess<'de>,
+{
+    type Error = D::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, D::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.inner.next_key_seed(Arena {
+            inner: seed,
+            bump: self.bump,
+        })
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, D::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.inner.next_value_seed(Arena {
+            inner: seed,
+            bump: self.bump,
+        })
+    }
+
+    fn next_entry_seed<K, V>(
+        &mut self,
+        kseed: K,
+        vseed: V,
+    ) -> Result<Option<(K::Value, V::Value)>, D::Error>
+    where
+        K: DeserializeSeed<'de>,
+        V: DeserializeSeed<'de>,
+    {
+        self.inner.next_entry_seed(
+            Arena {
+                inner: kseed,
+                bump: self.bump,
+            },
+            Arena {
+                inner: vseed,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, D> EnumAccess<'de> for Arena<'de, D>
+where
+    D: EnumAccess<'de>,
+{
+    type Error = D::Error;
+    type Variant = Arena<'de, D::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let bump = self.bump;
+        self.inner
+            .variant_seed(Arena { inner: seed, bump })
+            .map(|(value, variant)| {
+                (
+                    value,
+                    Arena {
+                        inner: variant,
+                        bump,
+                    },
+                )
+            })
+    }
+}
+
+impl<'de, D> VariantAccess<'de> for Arena<'de, D>
+where
+    D: VariantAccess<'de>,
+{
+    type Error = D::Error;
+
+    fn unit_variant(self) -> Result<(), D::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, D::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.inner.newtype_variant_seed(Arena {
+            inner: seed,
+            bump: self.bump,
+        })
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.tuple_variant(
+            len,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.struct_variant(
+            fields,
+            Arena {
+                inner: visitor,
+                bump: self.bump,
+            },
+        )
+    }
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::arena")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, encoding: Encoding) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize bytes as hex.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + crate::Serialize,
+    S: Serializer,
+{
+    crate::Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to borrow bytes from the input or from
+/// an [`Arena`].
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer, Encoding::Hex)
+}
+
+/// Base64 fields decoded into an [`Arena`].
+pub mod base64 {
+    use super::Deserialize;
+    use crate::dynamic::Encoding;
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize bytes as base64.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + crate::base64::Serialize,
+        S: Serializer,
+    {
+        crate::base64::Serialize::serialize(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function to borrow bytes from the input or
+    /// from an [`Arena`](super::Arena).
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, Encoding::Base64)
+    }
+}
+
+struct BorrowedVisitor;
+
+impl<'de> Visitor<'de> for BorrowedVisitor {
+    type Value = &'de [u8];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a borrowed byte array")
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<&'de [u8], E>
+    where
+        E: Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<&'de [u8], E>
+    where
+        E: Error,
+    {
+        Ok(v.as_bytes())
+    }
+
+    fn visit_newtype_struct<D>(self, _deserializer: D) -> Result<&'de [u8], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Err(D::Error::custom(
+            "human-readable byte fields must be deserialized through serde_human_bytes::arena::Arena",
+        ))
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a [u8] {
+    fn deserialize<D>(deserializer: D, encoding: Encoding) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_bytes(BorrowedVisitor);
+        }
+        let name = match encoding {
+            Encoding::Hex => HEX,
+            Encoding::Base64 => BASE64,
+        };
+        deserializer.deserialize_newtype_struct(name, BorrowedVisitor)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a Bytes {
+    fn deserialize<D>(deserializer: D, encoding: Encoding) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <&[u8]>::deserialize(deserializer, encoding).map(Bytes::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, encoding: Encoding) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            encoding: Encoding,
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer, self.encoding).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor {
+            encoding,
+            out: PhantomData,
+        };
+        deserializer.deserialize_option(visitor)
+    }
+}
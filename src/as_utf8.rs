@@ -0,0 +1,567 @@
+//! Bytes that are UTF-8 by convention, written as plain strings.
+//!
+//! Use `#[serde(with = "serde_human_bytes::as_utf8")]` for fields such as
+//! protocol text frames or Unix file names. Human-readable formats get the
+//! bytes as a string, and serializing bytes that are not valid UTF-8 is an
+//! error. With `#[serde(with = "serde_human_bytes::as_utf8::lossy")]`,
+//! invalid sequences are written as U+FFFD instead, which cannot be turned
+//! back into the original bytes. Compact formats get the bytes unchanged.
+//!
+//! Unlike hex, a string can be borrowed as bytes, so `&[u8]` and `Cow<[u8]>`
+//! fields deserialize from human-readable formats without copying whenever
+//! the format hands out borrowed strings.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Frame<'a> {
+//!     #[serde(with = "serde_human_bytes::as_utf8", borrow)]
+//!     text: &'a [u8],
+//!
+//!     #[serde(with = "serde_human_bytes::as_utf8::lossy")]
+//!     file_name: Vec<u8>,
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, Expected, Unexpected, Visitor};
+use serde::ser::Error as _;
+use serde::{Deserializer, Serializer};
+
+use crate::{ByteArray, Bytes};
+
+#[cfg(feature = "alloc")]
+use crate::ByteBuf;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// What to do with bytes that are not valid UTF-8, picked by the module the
+/// field is serialized with.
+#[derive(Copy, Clone, Debug)]
+pub struct Fallback {
+    lossy: bool,
+}
+
+/// Bytes with each invalid UTF-8 sequence replaced by U+FFFD.
+struct Lossy<'a>(&'a [u8]);
+
+impl<'a> Display for Lossy<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rest = self.0;
+        loop {
+            match str::from_utf8(rest) {
+                Ok(s) => return f.write_str(s),
+                Err(err) => {
+                    let (valid, invalid) = rest.split_at(err.valid_up_to());
+                    f.write_str(str::from_utf8(valid).unwrap_or_default())?;
+                    f.write_str("\u{fffd}")?;
+                    match err.error_len() {
+                        Some(len) => rest = &invalid[len..],
+                        None => return Ok(()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn serialize_text<S>(bytes: &[u8], serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(bytes);
+    }
+    match str::from_utf8(bytes) {
+        Ok(s) => serializer.serialize_str(s),
+        Err(_) if fallback.lossy => serializer.collect_str(&Lossy(bytes)),
+        Err(err) => Err(S::Error::custom(format_args!(
+            "invalid UTF-8 at offset {}",
+            err.valid_up_to()
+        ))),
+    }
+}
+
+struct Utf8Visitor<T> {
+    out: PhantomData<T>,
+}
+
+impl<T> Utf8Visitor<T> {
+    fn new() -> Self {
+        Utf8Visitor { out: PhantomData }
+    }
+
+    /// Check that `v` is UTF-8, for formats that hand strings out as bytes.
+    fn check<'a, E>(&self, v: &'a [u8]) -> Result<&'a str, E>
+    where
+        E: Error,
+        Self: Expected,
+    {
+        str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), self))
+    }
+}
+
+impl<'de: 'a, 'a> Visitor<'de> for Utf8Visitor<&'a [u8]> {
+    type Value = &'a [u8];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a borrowed UTF-8 string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<&'a [u8], E>
+    where
+        E: Error,
+    {
+        Ok(v.as_bytes())
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<&'a [u8], E>
+    where
+        E: Error,
+    {
+        self.check(v).map(str::as_bytes)
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for Utf8Visitor<[u8; N]> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a UTF-8 string of {} bytes", N)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        if v.len() != N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(v.as_bytes());
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let s = self.check(v)?;
+        self.visit_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for Utf8Visitor<Vec<u8>> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a UTF-8 string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        Ok(v.as_bytes().to_vec())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        Ok(v.into_bytes())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        self.check(v).map(|s| s.as_bytes().to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        self.check(&v)?;
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de: 'a, 'a> Visitor<'de> for Utf8Visitor<Cow<'a, [u8]>> {
+    type Value = Cow<'a, [u8]>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a UTF-8 string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Cow<'a, [u8]>, E>
+    where
+        E: Error,
+    {
+        Ok(Cow::Borrowed(v.as_bytes()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Cow<'a, [u8]>, E>
+    where
+        E: Error,
+    {
+        Ok(Cow::Owned(v.as_bytes().to_vec()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Cow<'a, [u8]>, E>
+    where
+        E: Error,
+    {
+        Ok(Cow::Owned(v.into_bytes()))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Cow<'a, [u8]>, E>
+    where
+        E: Error,
+    {
+        self.check(v).map(|s| Cow::Borrowed(s.as_bytes()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Cow<'a, [u8]>, E>
+    where
+        E: Error,
+    {
+        self.check(v).map(|s| Cow::Owned(s.as_bytes().to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Cow<'a, [u8]>, E>
+    where
+        E: Error,
+    {
+        self.check(&v)?;
+        Ok(Cow::Owned(v))
+    }
+}
+
+fn deserialize_text<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: crate::Deserialize<'de>,
+    Utf8Visitor<T>: Visitor<'de, Value = T>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(Utf8Visitor::new())
+    } else {
+        crate::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::as_utf8")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::as_utf8")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize UTF-8 bytes as a string,
+/// failing on invalid UTF-8.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer, Fallback { lossy: false })
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from a string.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+/// UTF-8 text with invalid sequences replaced by U+FFFD.
+pub mod lossy {
+    use super::{Deserialize, Fallback, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize bytes as a string,
+    /// replacing invalid UTF-8.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(bytes, serializer, Fallback { lossy: true })
+    }
+
+    /// Serde `deserialize_with` function to deserialize bytes from a string.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+}
+
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(&**self, serializer, fallback)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Box<[u8]> {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Serialize for Cow<'a, [u8]> {
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_text(self, serializer, fallback)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer, fallback)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S, fallback: Fallback) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsText<T> {
+            bytes: T,
+            fallback: Fallback,
+        }
+
+        impl<T> serde::Serialize for AsText<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.bytes.serialize(serializer, self.fallback)
+            }
+        }
+
+        match self {
+            Some(bytes) => serializer.serialize_some(&AsText { bytes, fallback }),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a [u8] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a Bytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer).map(Bytes::new)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer).map(ByteArray::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text::<Vec<u8>, D>(deserializer).map(ByteBuf::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Box<[u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer).map(Vec::into_boxed_slice)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_text(deserializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TextVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for TextVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional UTF-8 string")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = TextVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
@@ -25,6 +25,7 @@ mod de;
 mod ser;
 
 pub use de::Deserialize;
+pub(crate) use de::{decode_to_slice, decoded_len};
 pub use ser::Serialize;
 use serde::{Deserializer, Serializer};
 
@@ -1,27 +1,138 @@
+use crate::dynamic::Encoding;
+#[cfg(feature = "alloc")]
+use crate::error::ExpectedLen;
+use crate::seed;
 use crate::{ByteArray, Bytes};
-use core::convert::TryInto;
 use core::fmt;
 use core::marker::PhantomData;
-use serde::de::{Error, Visitor};
+use core::str;
+use serde::de::{Error, Unexpected, Visitor};
 use serde::Deserializer;
 
+#[cfg(feature = "alloc")]
 use crate::ByteBuf;
 
+#[cfg(feature = "alloc")]
 use core::cmp;
+#[cfg(feature = "alloc")]
+use core::convert::TryInto;
 
+#[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
 use serde::de::SeqAccess;
 
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+use crate::bytevec::{ByteVec, ByteVecVisitor};
+use ::base64::DecodeError;
+
+#[cfg(feature = "alloc")]
 pub(crate) fn deserialize_base64<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = serde::Deserialize::deserialize(deserializer)?;
-    ::base64::decode(s).map_err(D::Error::custom)
+    seed::decode_vec(&s, Encoding::Base64).map_err(|err| err.into_de_error(&"a base64 string"))
+}
+
+/// Number of bytes that valid base64 `input` decodes to.
+pub(crate) fn decoded_len(input: &[u8]) -> usize {
+    let mut len = input.len();
+    for _ in 0..2 {
+        if len > 0 && input[len - 1] == b'=' {
+            len -= 1;
+        }
+    }
+    len / 4 * 3 + (len % 4 * 3 / 4)
+}
+
+/// Decode base64 into `out` without allocating, returning the number of bytes
+/// written.
+///
+/// Accepts exactly the inputs accepted by `base64::decode`. Input that would
+/// decode to more than `out.len()` bytes is reported as `InvalidLength`.
+pub(crate) fn decode_to_slice(input: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+    // `decode_config_slice` may write past the decoded length into a buffer
+    // sized by the caller, so go through a scratch buffer one quad at a time.
+    let last = input.len().saturating_sub(1) / 4;
+    let mut written = 0;
+
+    for (i, chunk) in input.chunks(4).enumerate() {
+        let offset = i * 4;
+        if i != last {
+            if let Some(pos) = chunk.iter().position(|&b| b == b'=') {
+                return Err(DecodeError::InvalidByte(offset + pos, b'='));
+            }
+        }
+
+        let mut buf = [0; 3];
+        let n =
+            ::base64::decode_config_slice(chunk, ::base64::STANDARD, &mut buf).map_err(|err| {
+                match err {
+                    DecodeError::InvalidByte(pos, b) => DecodeError::InvalidByte(offset + pos, b),
+                    DecodeError::InvalidLastSymbol(pos, b) => {
+                        DecodeError::InvalidLastSymbol(offset + pos, b)
+                    }
+                    DecodeError::InvalidLength => DecodeError::InvalidLength,
+                }
+            })?;
+
+        let dst = out
+            .get_mut(written..written + n)
+            .ok_or(DecodeError::InvalidLength)?;
+        dst.copy_from_slice(&buf[..n]);
+        written += n;
+    }
+
+    Ok(written)
+}
+
+/// Decodes a base64 string straight into a fixed-size array, without
+/// allocating.
+struct Base64ArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for Base64ArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a base64 string of {} bytes", N)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        seed::decode_array(v, Encoding::Base64).map_err(|err| err.into_de_error(&self))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn deserialize_base64_array<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(Base64ArrayVisitor::<N>)
 }
 
 /// Types that can be deserialized via `#[serde(with = "serde_human_bytes")]`.
@@ -49,6 +160,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for &'a [u8] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for Vec<u8> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -84,9 +196,7 @@ impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
         D: Deserializer<'de>,
     {
         if deserializer.is_human_readable() {
-            deserialize_base64(deserializer)?
-                .try_into()
-                .map_err(|_| D::Error::custom("invalid array length"))
+            deserialize_base64_array(deserializer)
         } else {
             let arr: ByteArray<N> = serde::Deserialize::deserialize(deserializer)?;
             Ok(*arr)
@@ -116,10 +226,7 @@ impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
         D: Deserializer<'de>,
     {
         if deserializer.is_human_readable() {
-            deserialize_base64(deserializer)?
-                .try_into()
-                .map(ByteArray::new)
-                .map_err(|_| D::Error::custom("invalid array length"))
+            deserialize_base64_array(deserializer).map(ByteArray::new)
         } else {
             // Via the serde::Deserialize impl for ByteArray.
             serde::Deserialize::deserialize(deserializer)
@@ -143,6 +250,7 @@ impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for &'a ByteArray<N> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for ByteBuf {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -157,6 +265,7 @@ impl<'de> Deserialize<'de> for ByteBuf {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -236,6 +345,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, Bytes> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -255,6 +365,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, Bytes> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for Box<[u8]> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -268,6 +379,7 @@ impl<'de> Deserialize<'de> for Box<[u8]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for Box<Bytes> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -284,6 +396,104 @@ impl<'de> Deserialize<'de> for Box<Bytes> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'de, const N: usize> Deserialize<'de> for Box<[u8; N]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Decode on the heap so that large arrays never touch the stack.
+        let bytes: Box<[u8]> = Deserialize::deserialize(deserializer)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Box<[u8]>| D::Error::invalid_length(bytes.len(), &ExpectedLen(N)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Rc<[u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Deserialize::deserialize(deserializer)?;
+        Ok(bytes.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Rc<Bytes> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(Bytes::from_rc)
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<'de> Deserialize<'de> for Arc<[u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Deserialize::deserialize(deserializer)?;
+        Ok(bytes.into())
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<'de> Deserialize<'de> for Arc<Bytes> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(Bytes::from_arc)
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<'de, const N: usize> Deserialize<'de> for Arc<[u8; N]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Arc<[u8]> = Deserialize::deserialize(deserializer)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Arc<[u8]>| D::Error::invalid_length(bytes.len(), &ExpectedLen(N)))
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<'de> Deserialize<'de> for crate::SecretBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::SecretBytes::deserialize_as(deserializer, crate::secret::Encoding::Base64)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<'de, const N: usize> Deserialize<'de> for crate::SecretArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::SecretArray::deserialize_as(deserializer, crate::secret::Encoding::Base64)
+    }
+}
+
 impl<'de, T> Deserialize<'de> for Option<T>
 where
     T: Deserialize<'de>,
@@ -326,3 +536,82 @@ where
         deserializer.deserialize_option(visitor)
     }
 }
+
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+struct Base64ByteVecVisitor<T> {
+    out: PhantomData<T>,
+}
+
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+impl<'de, T> Visitor<'de> for Base64ByteVecVisitor<T>
+where
+    T: ByteVec,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a base64 string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: Error,
+    {
+        let len = decoded_len(v.as_bytes());
+        if len > T::MAX_LEN {
+            return Err(E::invalid_length(len, &ByteVecVisitor::<T>::new()));
+        }
+        let mut bytes = T::new();
+        bytes.resize_zeroed(len);
+        let written = decode_to_slice(v.as_bytes(), bytes.as_mut_slice())
+            .map_err(|err| crate::DecodeError::from_base64(&err, v).into_de_error(&self))?;
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+}
+
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+fn deserialize_byte_vec<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: ByteVec,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(Base64ByteVecVisitor { out: PhantomData })
+    } else {
+        deserializer.deserialize_bytes(ByteVecVisitor::new())
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'de, A> Deserialize<'de> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array<Item = u8>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_byte_vec(deserializer)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'de, const CAP: usize> Deserialize<'de> for arrayvec::ArrayVec<u8, CAP> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_byte_vec(deserializer)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, const N: usize> Deserialize<'de> for heapless::Vec<u8, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_byte_vec(deserializer)
+    }
+}
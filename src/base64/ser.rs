@@ -1,12 +1,25 @@
 use crate::{ByteArray, Bytes};
+use base64::display::Base64Display;
 use serde::Serializer;
 
+#[cfg(feature = "alloc")]
 use crate::ByteBuf;
 
+#[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
 
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+use alloc::sync::Arc;
+
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
 pub(crate) fn serialize_bytes<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
@@ -14,7 +27,7 @@ where
     S: Serializer,
 {
     if serializer.is_human_readable() {
-        serializer.serialize_str(&base64::encode(bytes))
+        serializer.collect_str(&Base64Display::with_config(bytes, base64::STANDARD))
     } else {
         serializer.serialize_bytes(bytes)
     }
@@ -37,6 +50,7 @@ impl Serialize for [u8] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Serialize for Vec<u8> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -73,6 +87,7 @@ impl<const N: usize> Serialize for ByteArray<N> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Serialize for ByteBuf {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -82,6 +97,7 @@ impl Serialize for ByteBuf {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Serialize for Cow<'a, [u8]> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -91,6 +107,7 @@ impl<'a> Serialize for Cow<'a, [u8]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Serialize for Cow<'a, Bytes> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -100,6 +117,39 @@ impl<'a> Serialize for Cow<'a, Bytes> {
     }
 }
 
+#[cfg(feature = "smallvec")]
+impl<A> Serialize for smallvec::SmallVec<A>
+where
+    A: smallvec::Array<Item = u8>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> Serialize for arrayvec::ArrayVec<u8, CAP> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> Serialize for heapless::Vec<u8, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer)
+    }
+}
+
 impl<T> Serialize for &T
 where
     T: ?Sized + Serialize,
@@ -112,6 +162,27 @@ where
     }
 }
 
+#[cfg(feature = "secret")]
+impl Serialize for crate::SecretBytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_as(serializer, crate::secret::Encoding::Base64)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<const N: usize> Serialize for crate::SecretArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_as(serializer, crate::secret::Encoding::Base64)
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<T> Serialize for Box<T>
 where
     T: ?Sized + Serialize,
@@ -124,6 +195,35 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T> Serialize for Rc<T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<T> Serialize for Arc<T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
 impl<T> Serialize for Option<T>
 where
     T: Serialize,
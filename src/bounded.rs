@@ -0,0 +1,396 @@
+//! Byte buffers with a length limit enforced while deserializing.
+//!
+//! [`BoundedByteBuf<MIN, MAX>`](crate::BoundedByteBuf) and the
+//! [`Bounded<MIN, MAX>`] `with` path reject input that decodes to fewer than
+//! `MIN` or more than `MAX` bytes. In human-readable formats the length of
+//! the hex string is checked before anything is decoded or allocated.
+//!
+//! Compact formats are different: a format such as bincode reads a byte
+//! string of the length its prefix declares before this crate gets to check
+//! it, so a hostile length prefix can still make it allocate. Use the
+//! format's own limit for untrusted input, such as bincode's
+//! `Options::with_limit`.
+//!
+//! ```
+//! # use serde_derive::Deserialize;
+//! use serde::Deserialize;
+//! use serde_human_bytes::BoundedByteBuf;
+//!
+//! #[derive(Deserialize)]
+//! struct Upload {
+//!     #[serde(with = "serde_human_bytes::bounded::Bounded::<0, 4096>")]
+//!     body: Vec<u8>,
+//!
+//!     nonce: BoundedByteBuf<12, 24>,
+//! }
+//! ```
+//!
+//! Oversized strings are reported with their actual length, as in `invalid
+//! length 5000, expected a hex string of 0 to 4096 bytes`. Sequences are
+//! rejected at element `MAX + 1` without reading the rest. Bounds with `MIN`
+//! greater than `MAX` fail to compile.
+
+use core::cmp;
+use core::convert::TryFrom;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+use serde::ser::Serializer;
+use serde::Deserializer;
+
+use crate::display::HexDebug;
+use crate::ser::serialize_bytes;
+use crate::{ByteBuf, DecodeError};
+
+/// Wrapper around `Vec<u8>` holding between `MIN` and `MAX` bytes.
+///
+/// Serializes like [`ByteBuf`]. Deserializing from a human-readable format
+/// checks the length of the input before decoding it, so a hostile document
+/// cannot make it allocate more than `MAX` bytes. Compact formats need their
+/// own size limit for that, as described in the [module
+/// documentation](crate::bounded).
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use serde_human_bytes::BoundedByteBuf;
+///
+/// let nonce = BoundedByteBuf::<1, 4>::try_from(vec![1, 2, 3]).unwrap();
+/// assert_eq!(nonce.len(), 3);
+/// assert!(BoundedByteBuf::<1, 4>::try_from(vec![0; 5]).is_err());
+/// ```
+///
+/// `MIN` may not exceed `MAX`:
+///
+/// ```compile_fail
+/// # use std::convert::TryFrom;
+/// # use serde_human_bytes::BoundedByteBuf;
+/// let _ = BoundedByteBuf::<4, 1>::try_from(vec![0; 2]);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedByteBuf<const MIN: usize, const MAX: usize> {
+    bytes: Vec<u8>,
+}
+
+impl<const MIN: usize, const MAX: usize> BoundedByteBuf<MIN, MAX> {
+    /// Unwrap the vector of bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Format as `0x`-prefixed hex with `{:?}`, unlike the `Debug` impl of
+    /// `BoundedByteBuf` itself.
+    pub fn hex_debug(&self) -> HexDebug<'_> {
+        HexDebug(&self.bytes)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> TryFrom<Vec<u8>> for BoundedByteBuf<MIN, MAX> {
+    type Error = Vec<u8>;
+
+    /// Wrap `bytes`, or give them back if their length is out of bounds.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Vec<u8>> {
+        if (MIN..=Bounded::<MIN, MAX>::MAX_LEN).contains(&bytes.len()) {
+            Ok(BoundedByteBuf { bytes })
+        } else {
+            Err(bytes)
+        }
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> From<BoundedByteBuf<MIN, MAX>> for ByteBuf {
+    fn from(bytes: BoundedByteBuf<MIN, MAX>) -> Self {
+        ByteBuf::from(bytes.bytes)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Debug for BoundedByteBuf<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.bytes, f)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> AsRef<[u8]> for BoundedByteBuf<MIN, MAX> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Deref for BoundedByteBuf<MIN, MAX> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> DerefMut for BoundedByteBuf<MIN, MAX> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> serde::Serialize for BoundedByteBuf<MIN, MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(&self.bytes, serializer)
+    }
+}
+
+impl<'de, const MIN: usize, const MAX: usize> serde::Deserialize<'de> for BoundedByteBuf<MIN, MAX> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_bounded(deserializer, MIN, Bounded::<MIN, MAX>::MAX_LEN)
+            .map(|bytes| BoundedByteBuf { bytes })
+    }
+}
+
+struct BoundedVisitor {
+    min: usize,
+    max: usize,
+    hex: bool,
+}
+
+impl BoundedVisitor {
+    fn check<E>(&self, len: usize) -> Result<(), E>
+    where
+        E: Error,
+    {
+        if len < self.min || len > self.max {
+            Err(E::invalid_length(len, self))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for BoundedVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let what = if self.hex {
+            "a hex string"
+        } else {
+            "a byte array"
+        };
+        write!(formatter, "{} of {} to {} bytes", what, self.min, self.max)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        if !self.hex {
+            return self.visit_bytes(v.as_bytes());
+        }
+        self.check(v.len() / 2)?;
+        if v.len() % 2 != 0 {
+            return Err(DecodeError::OddLength.into_de_error(&self));
+        }
+        let mut bytes = alloc::vec![0; v.len() / 2];
+        hex::decode_to_slice(v, &mut bytes)
+            .map_err(|err| DecodeError::from_hex(err, v, bytes.len()).into_de_error(&self))?;
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        if self.hex {
+            let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+            return self.visit_str(s);
+        }
+        self.check(v.len())?;
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        if self.hex {
+            return self.visit_bytes(&v);
+        }
+        self.check(v.len())?;
+        Ok(v)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        if self.hex {
+            return self.visit_str(&v);
+        }
+        self.visit_byte_buf(v.into_bytes())
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Vec<u8>, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        if let Some(len) = visitor.size_hint() {
+            if len > self.max {
+                return Err(V::Error::invalid_length(len, &self));
+            }
+        }
+
+        let len = cmp::min(visitor.size_hint().unwrap_or(0), 4096);
+        let mut bytes = Vec::with_capacity(len);
+
+        while let Some(b) = visitor.next_element()? {
+            if bytes.len() == self.max {
+                return Err(V::Error::invalid_length(self.max + 1, &self));
+            }
+            bytes.push(b);
+        }
+
+        self.check(bytes.len())?;
+        Ok(bytes)
+    }
+}
+
+fn deserialize_bounded<'de, D>(deserializer: D, min: usize, max: usize) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BoundedVisitor {
+            min,
+            max,
+            hex: true,
+        })
+    } else {
+        deserializer.deserialize_byte_buf(BoundedVisitor {
+            min,
+            max,
+            hex: false,
+        })
+    }
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::bounded::Bounded::<MIN, MAX>")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, min: usize, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D, min: usize, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_bounded(deserializer, min, max)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D, min: usize, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_bounded(deserializer, min, max).map(ByteBuf::from)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<[u8]> {
+    fn deserialize<D>(deserializer: D, min: usize, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_bounded(deserializer, min, max).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, min: usize, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            min: usize,
+            max: usize,
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer, self.min, self.max).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor {
+            min,
+            max,
+            out: PhantomData,
+        };
+        deserializer.deserialize_option(visitor)
+    }
+}
+
+/// Serde `with` path limiting a byte field to between `MIN` and `MAX` bytes:
+/// `#[serde(with = "serde_human_bytes::bounded::Bounded::<MIN, MAX>")]`.
+///
+/// Serializes like `#[serde(with = "serde_human_bytes")]`.
+pub struct Bounded<const MIN: usize, const MAX: usize>;
+
+impl<const MIN: usize, const MAX: usize> Bounded<MIN, MAX> {
+    /// `MAX`, computed so that `MIN > MAX` overflows and fails to compile.
+    /// Const panics such as `assert!` need Rust 1.57.
+    const MAX_LEN: usize = MIN + (MAX - MIN);
+
+    /// Serde `serialize_with` function to serialize bytes efficiently.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + crate::Serialize,
+        S: Serializer,
+    {
+        crate::Serialize::serialize(bytes, serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize at most `MAX` bytes.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, MIN, Self::MAX_LEN)
+    }
+}
@@ -1,12 +1,19 @@
-use crate::de::deserialize_hex;
+use crate::de::deserialize_hex_array;
+use crate::display::{self, HexDebug};
+use crate::dynamic::Encoding;
+use crate::seed::{self, deserialize_into_slice};
 use crate::ser::serialize_bytes;
-use crate::Bytes;
+use crate::{Bytes, DecodeError};
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
 use core::convert::TryInto as _;
-use core::fmt::{self, Debug};
+use core::fmt::{self, Debug, Display, LowerHex, UpperHex};
 use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
 use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
@@ -53,6 +60,67 @@ impl<const N: usize> ByteArray<N> {
         self.bytes
     }
 
+    /// Decode a hex string of exactly `2 * N` digits, accepting exactly what
+    /// the human-readable deserializer of `#[serde(with = "serde_human_bytes")]`
+    /// accepts and failing with the same error.
+    ///
+    /// ```
+    /// use serde_human_bytes::{ByteArray, DecodeError};
+    ///
+    /// let array = ByteArray::<3>::from_hex("414243").unwrap();
+    /// assert_eq!(array, b"ABC");
+    /// assert_eq!(
+    ///     ByteArray::<4>::from_hex("414243"),
+    ///     Err(DecodeError::InvalidLength { expected: 4, actual: 3 }),
+    /// );
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Self, DecodeError> {
+        seed::decode_array(s, Encoding::Hex).map(ByteArray::new)
+    }
+
+    /// Decode a base64 string of exactly `N` bytes, accepting exactly what the
+    /// human-readable deserializer of
+    /// `#[serde(with = "serde_human_bytes::base64")]` accepts and failing with
+    /// the same error.
+    pub fn from_base64(s: &str) -> Result<Self, DecodeError> {
+        seed::decode_array(s, Encoding::Base64).map(ByteArray::new)
+    }
+
+    /// Encode as lowercase hex, as the human-readable serializer does.
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.bytes)
+    }
+
+    /// Encode as padded standard base64, as the human-readable serializer of
+    /// `serde_human_bytes::base64` does.
+    #[cfg(feature = "alloc")]
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.bytes)
+    }
+
+    /// Write the lowercase hex encoding into `out` without allocating.
+    pub fn encode_to<W>(&self, out: &mut W) -> fmt::Result
+    where
+        W: ?Sized + fmt::Write,
+    {
+        display::write_hex(out, &self.bytes, false)
+    }
+
+    /// Write the base64 encoding into `out` without allocating.
+    pub fn encode_base64_to<W>(&self, out: &mut W) -> fmt::Result
+    where
+        W: ?Sized + fmt::Write,
+    {
+        display::write_base64(out, &self.bytes)
+    }
+
+    /// Format as `0x`-prefixed hex with `{:?}`, unlike the `Debug` impl of
+    /// `ByteArray` itself.
+    pub fn hex_debug(&self) -> HexDebug<'_> {
+        HexDebug(&self.bytes)
+    }
+
     fn from_ref(bytes: &[u8; N]) -> &Self {
         unsafe { &*(bytes as *const [u8; N] as *const ByteArray<N>) }
     }
@@ -64,6 +132,24 @@ impl<const N: usize> Debug for ByteArray<N> {
     }
 }
 
+impl<const N: usize> Display for ByteArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, false)
+    }
+}
+
+impl<const N: usize> LowerHex for ByteArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, false)
+    }
+}
+
+impl<const N: usize> UpperHex for ByteArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, true)
+    }
+}
+
 impl<const N: usize> Default for ByteArray<N> {
     fn default() -> Self {
         ByteArray { bytes: [0; N] }
@@ -126,6 +212,16 @@ impl<const N: usize> From<[u8; N]> for ByteArray<N> {
     }
 }
 
+impl<const N: usize> FromStr for ByteArray<N> {
+    type Err = DecodeError;
+
+    /// Parse a hex string of exactly `2 * N` digits, exactly as the
+    /// human-readable deserializer does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ByteArray::from_hex(s)
+    }
+}
+
 impl<Rhs, const N: usize> PartialEq<Rhs> for ByteArray<N>
 where
     Rhs: ?Sized + Borrow<[u8; N]>,
@@ -235,14 +331,18 @@ impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
         D: Deserializer<'de>,
     {
         if deserializer.is_human_readable() {
-            deserialize_hex(deserializer)?
-                .try_into()
-                .map(ByteArray::new)
-                .map_err(|_| D::Error::custom("invalid length"))
+            deserialize_hex_array(deserializer).map(ByteArray::new)
         } else {
             deserializer.deserialize_bytes(ByteArrayVisitor::<N>)
         }
     }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_into_slice(deserializer, &mut place.bytes, Encoding::Hex, true).map(drop)
+    }
 }
 
 struct BorrowedByteArrayVisitor<const N: usize>;
@@ -1,8 +1,9 @@
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::{self, Ordering};
-use core::fmt::{self, Debug};
+use core::fmt::{self, Debug, Display, LowerHex, UpperHex};
 use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
 
 use alloc::boxed::Box;
 use alloc::string::String;
@@ -12,8 +13,11 @@ use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 
 use crate::de::deserialize_hex;
+use crate::display::{self, HexDebug};
+use crate::dynamic::Encoding;
+use crate::seed::{self, deserialize_into_buf};
 use crate::ser::serialize_bytes;
-use crate::Bytes;
+use crate::{Bytes, DecodeError};
 
 /// Wrapper around `Vec<u8>` to serialize and deserialize efficiently.
 ///
@@ -62,6 +66,61 @@ impl ByteBuf {
         }
     }
 
+    /// Decode a hex string, accepting exactly what the human-readable
+    /// deserializer of `#[serde(with = "serde_human_bytes")]` accepts and
+    /// failing with the same error.
+    ///
+    /// ```
+    /// use serde_human_bytes::ByteBuf;
+    ///
+    /// let buf = ByteBuf::from_hex("414243").unwrap();
+    /// assert_eq!(buf, b"ABC");
+    /// assert_eq!(buf.to_hex(), "414243");
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Self, DecodeError> {
+        seed::decode_vec(s, Encoding::Hex).map(ByteBuf::from)
+    }
+
+    /// Decode a base64 string, accepting exactly what the human-readable
+    /// deserializer of `#[serde(with = "serde_human_bytes::base64")]` accepts
+    /// and failing with the same error.
+    pub fn from_base64(s: &str) -> Result<Self, DecodeError> {
+        seed::decode_vec(s, Encoding::Base64).map(ByteBuf::from)
+    }
+
+    /// Encode as lowercase hex, as the human-readable serializer does.
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    /// Encode as padded standard base64, as the human-readable serializer of
+    /// `serde_human_bytes::base64` does.
+    pub fn to_base64(&self) -> String {
+        base64::encode(&self.bytes)
+    }
+
+    /// Write the lowercase hex encoding into `out` without allocating.
+    pub fn encode_to<W>(&self, out: &mut W) -> fmt::Result
+    where
+        W: ?Sized + fmt::Write,
+    {
+        display::write_hex(out, &self.bytes, false)
+    }
+
+    /// Write the base64 encoding into `out` without allocating.
+    pub fn encode_base64_to<W>(&self, out: &mut W) -> fmt::Result
+    where
+        W: ?Sized + fmt::Write,
+    {
+        display::write_base64(out, &self.bytes)
+    }
+
+    /// Format as `0x`-prefixed hex with `{:?}`, unlike the `Debug` impl of
+    /// `ByteBuf` itself.
+    pub fn hex_debug(&self) -> HexDebug<'_> {
+        HexDebug(&self.bytes)
+    }
+
     /// Unwrap the vector of byte underlying this `ByteBuf`.
     pub fn into_vec(self) -> Vec<u8> {
         self.bytes
@@ -92,6 +151,24 @@ impl Debug for ByteBuf {
     }
 }
 
+impl Display for ByteBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, false)
+    }
+}
+
+impl LowerHex for ByteBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, false)
+    }
+}
+
+impl UpperHex for ByteBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, true)
+    }
+}
+
 impl AsRef<[u8]> for ByteBuf {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
@@ -136,6 +213,15 @@ impl From<Vec<u8>> for ByteBuf {
     }
 }
 
+impl FromStr for ByteBuf {
+    type Err = DecodeError;
+
+    /// Parse a hex string, exactly as the human-readable deserializer does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ByteBuf::from_hex(s)
+    }
+}
+
 impl<Rhs> PartialEq<Rhs> for ByteBuf
 where
     Rhs: ?Sized + AsRef<[u8]>,
@@ -259,4 +345,11 @@ impl<'de> Deserialize<'de> for ByteBuf {
             deserializer.deserialize_byte_buf(ByteBufVisitor)
         }
     }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_into_buf(deserializer, &mut place.bytes, Encoding::Hex)
+    }
 }
@@ -1,13 +1,24 @@
 use core::cmp::Ordering;
-use core::fmt::{self, Debug};
+use core::fmt::{self, Debug, Display, LowerHex, UpperHex};
 use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
 
+#[cfg(feature = "alloc")]
 use alloc::borrow::ToOwned;
 
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
-
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+use alloc::sync::Arc;
+
+use crate::display::{self, HexDebug};
 use crate::ser::serialize_bytes;
+#[cfg(feature = "alloc")]
 use crate::ByteBuf;
 
 use serde::de::{Deserialize, Deserializer, Error};
@@ -45,6 +56,30 @@ impl Bytes {
     pub fn new(bytes: &[u8]) -> &Self {
         unsafe { &*(bytes as *const [u8] as *const Bytes) }
     }
+
+    /// Format as `0x`-prefixed hex with `{:?}`, unlike the `Debug` impl of
+    /// `Bytes` itself.
+    pub fn hex_debug(&self) -> HexDebug<'_> {
+        HexDebug(&self.bytes)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_rc(bytes: Rc<[u8]>) -> Rc<Self> {
+        // SAFETY: `Bytes` is `#[repr(transparent)]` over `[u8]`, so the
+        // allocation has the layout `Rc<Bytes>` expects and the pointer keeps
+        // its slice length as metadata.
+        unsafe { Rc::from_raw(Rc::into_raw(bytes) as *const Bytes) }
+    }
+
+    #[cfg(all(
+        feature = "alloc",
+        any(no_target_has_atomic, target_has_atomic = "ptr")
+    ))]
+    pub(crate) fn from_arc(bytes: Arc<[u8]>) -> Arc<Self> {
+        // SAFETY: as in `from_rc`, `Bytes` is `#[repr(transparent)]` over
+        // `[u8]`.
+        unsafe { Arc::from_raw(Arc::into_raw(bytes) as *const Bytes) }
+    }
 }
 
 impl Debug for Bytes {
@@ -53,6 +88,24 @@ impl Debug for Bytes {
     }
 }
 
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, false)
+    }
+}
+
+impl LowerHex for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, false)
+    }
+}
+
+impl UpperHex for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display::fmt_hex(&self.bytes, f, true)
+    }
+}
+
 impl AsRef<[u8]> for Bytes {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
@@ -85,6 +138,7 @@ impl<'a> From<&'a [u8]> for &'a Bytes {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl ToOwned for Bytes {
     type Owned = ByteBuf;
 
@@ -93,6 +147,7 @@ impl ToOwned for Bytes {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<Box<[u8]>> for Box<Bytes> {
     fn from(bytes: Box<[u8]>) -> Self {
         unsafe { Box::from_raw(Box::into_raw(bytes) as *mut Bytes) }
@@ -105,6 +160,7 @@ impl Default for &Bytes {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for Box<Bytes> {
     fn default() -> Self {
         ByteBuf::new().into_boxed_bytes()
@@ -0,0 +1,225 @@
+//! Support for third-party vector types, some of which have a fixed capacity.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Error, SeqAccess, Visitor};
+
+use crate::DecodeError;
+
+/// A growable byte container that may refuse to grow past `MAX_LEN` bytes.
+///
+/// Callers are responsible for checking `MAX_LEN` before pushing; the
+/// deserializers report an invalid length instead of panicking or truncating.
+pub(crate) trait ByteVec: Sized {
+    const MAX_LEN: usize;
+
+    fn new() -> Self;
+
+    fn push(&mut self, byte: u8);
+
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+
+    fn resize_zeroed(&mut self, len: usize);
+
+    fn truncate(&mut self, len: usize);
+
+    fn as_mut_slice(&mut self) -> &mut [u8];
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> ByteVec for smallvec::SmallVec<A>
+where
+    A: smallvec::Array<Item = u8>,
+{
+    // Spills onto the heap rather than running out of space.
+    const MAX_LEN: usize = usize::MAX;
+
+    fn new() -> Self {
+        smallvec::SmallVec::new()
+    }
+
+    fn push(&mut self, byte: u8) {
+        smallvec::SmallVec::push(self, byte);
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        smallvec::SmallVec::extend_from_slice(self, bytes);
+    }
+
+    fn resize_zeroed(&mut self, len: usize) {
+        smallvec::SmallVec::resize(self, len, 0);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        smallvec::SmallVec::truncate(self, len);
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> ByteVec for arrayvec::ArrayVec<u8, CAP> {
+    const MAX_LEN: usize = CAP;
+
+    fn new() -> Self {
+        arrayvec::ArrayVec::new()
+    }
+
+    fn push(&mut self, byte: u8) {
+        arrayvec::ArrayVec::push(self, byte);
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.try_extend_from_slice(bytes).unwrap();
+    }
+
+    fn resize_zeroed(&mut self, len: usize) {
+        self.clear();
+        self.extend(core::iter::repeat(0).take(len));
+    }
+
+    fn truncate(&mut self, len: usize) {
+        arrayvec::ArrayVec::truncate(self, len);
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> ByteVec for heapless::Vec<u8, N> {
+    const MAX_LEN: usize = N;
+
+    fn new() -> Self {
+        heapless::Vec::new()
+    }
+
+    fn push(&mut self, byte: u8) {
+        heapless::Vec::push(self, byte).unwrap();
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        heapless::Vec::extend_from_slice(self, bytes).unwrap();
+    }
+
+    fn resize_zeroed(&mut self, len: usize) {
+        self.resize_default(len).unwrap();
+    }
+
+    fn truncate(&mut self, len: usize) {
+        heapless::Vec::truncate(self, len);
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+fn expecting<T: ByteVec>(formatter: &mut fmt::Formatter) -> fmt::Result {
+    if T::MAX_LEN == usize::MAX {
+        formatter.write_str("byte array")
+    } else {
+        write!(formatter, "a byte array of at most {} bytes", T::MAX_LEN)
+    }
+}
+
+pub(crate) struct ByteVecVisitor<T> {
+    out: PhantomData<T>,
+}
+
+impl<T> ByteVecVisitor<T> {
+    pub(crate) fn new() -> Self {
+        ByteVecVisitor { out: PhantomData }
+    }
+}
+
+impl<'de, T> Visitor<'de> for ByteVecVisitor<T>
+where
+    T: ByteVec,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        expecting::<T>(formatter)
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<T, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut bytes = T::new();
+        let mut len = 0;
+
+        while let Some(b) = visitor.next_element()? {
+            if len == T::MAX_LEN {
+                return Err(V::Error::invalid_length(len + 1, &self));
+            }
+            bytes.push(b);
+            len += 1;
+        }
+
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<T, E>
+    where
+        E: Error,
+    {
+        if v.len() > T::MAX_LEN {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut bytes = T::new();
+        bytes.extend_from_slice(v);
+        Ok(bytes)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+}
+
+pub(crate) struct HexByteVecVisitor<T> {
+    out: PhantomData<T>,
+}
+
+impl<T> HexByteVecVisitor<T> {
+    pub(crate) fn new() -> Self {
+        HexByteVecVisitor { out: PhantomData }
+    }
+}
+
+impl<'de, T> Visitor<'de> for HexByteVecVisitor<T>
+where
+    T: ByteVec,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        expecting::<T>(formatter)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: Error,
+    {
+        if v.len() % 2 != 0 {
+            return Err(DecodeError::OddLength.into_de_error(&self));
+        }
+        let len = v.len() / 2;
+        if len > T::MAX_LEN {
+            return Err(E::invalid_length(len, &self));
+        }
+        let mut bytes = T::new();
+        bytes.resize_zeroed(len);
+        hex::decode_to_slice(v, bytes.as_mut_slice())
+            .map_err(|err| DecodeError::from_hex(err, v, len).into_de_error(&self))?;
+        Ok(bytes)
+    }
+}
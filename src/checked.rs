@@ -0,0 +1,708 @@
+//! Hand-typeable encodings with a trailing checksum.
+//!
+//! These modules append the big-endian CRC-32 of the bytes before encoding
+//! them, so that a mistyped or truncated identifier fails to deserialize
+//! instead of silently producing different bytes:
+//!
+//! - `#[serde(with = "serde_human_bytes::checked")]` — lowercase hex, as in
+//!   `"414243a3830348"` for the bytes `ABC`
+//! - `#[serde(with = "serde_human_bytes::checked::crockford")]` — Crockford
+//!   base32, as in `"851478W30D40"`
+//! - `#[serde(with = "serde_human_bytes::checked::Grouped::<4>")]` and
+//!   `#[serde(with = "serde_human_bytes::checked::crockford::Grouped::<4>")]`
+//!   — the same, split into dash-separated groups of 4 characters, as in
+//!   `"4142-43a3-8303-48"`
+//!
+//! Compact formats get the bytes without a checksum.
+//!
+//! Deserializing ignores dashes anywhere in the input and accepts uppercase
+//! hex. Crockford input is case-insensitive and reads `I` and `L` as `1` and
+//! `O` as `0`. Malformed input is reported with the offending character or
+//! length, including input too short to hold the 4-byte checksum, while a
+//! well-formed string whose checksum does not match is reported as a
+//! [`ChecksumMismatch`](crate::DecodeError::ChecksumMismatch).
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Device {
+//!     #[serde(with = "serde_human_bytes::checked::Grouped::<4>")]
+//!     id: [u8; 8],
+//!
+//!     #[serde(with = "serde_human_bytes::checked::crockford::Grouped::<5>")]
+//!     recovery_code: Vec<u8>,
+//! }
+//! ```
+
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Write};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, Expected, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::{ByteArray, Bytes, DecodeError};
+
+#[cfg(feature = "alloc")]
+use crate::ByteBuf;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The alphabet and grouping of a checksummed field, picked by the module the
+/// field is serialized with.
+#[derive(Copy, Clone, Debug)]
+pub struct Format {
+    alphabet: Alphabet,
+    group: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Alphabet {
+    Hex,
+    Crockford,
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+impl Alphabet {
+    /// Call `f` with each digit of the encoding of `bytes`.
+    fn encode<F>(self, bytes: impl Iterator<Item = u8>, mut f: F)
+    where
+        F: FnMut(u8),
+    {
+        match self {
+            Alphabet::Hex => {
+                for b in bytes {
+                    f(HEX[usize::from(b >> 4)]);
+                    f(HEX[usize::from(b & 0xf)]);
+                }
+            }
+            Alphabet::Crockford => {
+                let mut buffer = 0u16;
+                let mut bits = 0;
+                for b in bytes {
+                    buffer = buffer << 8 | u16::from(b);
+                    bits += 8;
+                    while bits >= 5 {
+                        bits -= 5;
+                        f(CROCKFORD[usize::from(buffer >> bits & 0x1f)]);
+                    }
+                }
+                if bits > 0 {
+                    f(CROCKFORD[usize::from(buffer << (5 - bits) & 0x1f)]);
+                }
+            }
+        }
+    }
+
+    fn value(self, digit: u8) -> Option<u8> {
+        match self {
+            Alphabet::Hex => match digit {
+                b'0'..=b'9' => Some(digit - b'0'),
+                b'a'..=b'f' => Some(digit - b'a' + 10),
+                b'A'..=b'F' => Some(digit - b'A' + 10),
+                _ => None,
+            },
+            Alphabet::Crockford => match digit.to_ascii_uppercase() {
+                b'O' => Some(0),
+                b'I' | b'L' => Some(1),
+                digit => CROCKFORD
+                    .iter()
+                    .position(|&c| c == digit)
+                    .and_then(|i| u8::try_from(i).ok()),
+            },
+        }
+    }
+
+    /// The number of bytes encoded by the digits of `input`, including the
+    /// checksum.
+    fn decoded_len(self, input: &str) -> Result<usize, DecodeError> {
+        let digits = input.bytes().filter(|&b| b != b'-').count();
+        match self {
+            Alphabet::Hex if digits % 2 == 0 => Ok(digits / 2),
+            Alphabet::Hex => Err(DecodeError::OddLength),
+            Alphabet::Crockford => {
+                let len = digits * 5 / 8;
+                if (len * 8 + 4) / 5 == digits {
+                    Ok(len)
+                } else {
+                    Err(DecodeError::InvalidPadding { index: input.len() })
+                }
+            }
+        }
+    }
+
+    /// Decode the digits of `input`, skipping dashes, into `out` followed by
+    /// the checksum, which is returned.
+    fn decode(self, input: &str, out: &mut [u8]) -> Result<u32, DecodeError> {
+        let mut checksum = [0; 4];
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        let mut len = 0;
+        let mut last = 0;
+        for (index, c) in input.char_indices().filter(|&(_, c)| c != '-') {
+            let width = match self {
+                Alphabet::Hex => 4,
+                Alphabet::Crockford => 5,
+            };
+            let value = Some(c)
+                .filter(char::is_ascii)
+                .and_then(|c| self.value(c as u8));
+            let value = value.ok_or(DecodeError::InvalidCharacter { c, index })?;
+            buffer = buffer << width | u16::from(value);
+            bits += width;
+            last = index;
+            if bits >= 8 {
+                bits -= 8;
+                // Only the low byte is new; anything above it was emitted already.
+                let b = (buffer >> bits).to_le_bytes()[0];
+                match out.get_mut(len) {
+                    Some(slot) => *slot = b,
+                    None => checksum[len - out.len()] = b,
+                }
+                len += 1;
+            }
+        }
+        // Leftover bits of a final base32 digit must be zero, so that every
+        // byte string has exactly one encoding.
+        if buffer & ((1 << bits) - 1) != 0 {
+            return Err(DecodeError::InvalidPadding { index: last });
+        }
+        Ok(u32::from_be_bytes(checksum))
+    }
+}
+
+/// Bytes and their checksum, encoded.
+struct Checked<'a> {
+    bytes: &'a [u8],
+    format: Format,
+}
+
+impl<'a> Display for Checked<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let checksum = crc32(self.bytes).to_be_bytes();
+        let bytes = self.bytes.iter().chain(&checksum).copied();
+        let group = self.format.group;
+        let mut count = 0;
+        let mut result = Ok(());
+        self.format.alphabet.encode(bytes, |digit| {
+            if group != 0 && count != 0 && count % group == 0 {
+                result = result.and_then(|()| f.write_char('-'));
+            }
+            result = result.and_then(|()| f.write_char(char::from(digit)));
+            count += 1;
+        });
+        result
+    }
+}
+
+fn serialize_bytes<S>(bytes: &[u8], serializer: S, format: Format) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Checked { bytes, format })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+struct CheckedVisitor<T> {
+    alphabet: Alphabet,
+    out: PhantomData<T>,
+}
+
+impl<T> CheckedVisitor<T> {
+    fn new(format: Format) -> Self {
+        CheckedVisitor {
+            alphabet: format.alphabet,
+            out: PhantomData,
+        }
+    }
+
+    /// The length of the payload of `v`, not counting the checksum. Input
+    /// too short to hold a checksum has the wrong length.
+    fn payload_len<E>(&self, v: &str) -> Result<usize, E>
+    where
+        E: Error,
+        Self: Expected,
+    {
+        match self.alphabet.decoded_len(v) {
+            Ok(len) if len >= 4 => Ok(len - 4),
+            Ok(len) => Err(DecodeError::InvalidLength {
+                expected: 4,
+                actual: len,
+            }
+            .into_de_error(self)),
+            Err(err) => Err(err.into_de_error(self)),
+        }
+    }
+
+    fn decode<E>(&self, v: &str, out: &mut [u8]) -> Result<(), E>
+    where
+        E: Error,
+        Self: Expected,
+    {
+        match self.alphabet.decode(v, out) {
+            Ok(checksum) if checksum == crc32(out) => Ok(()),
+            Ok(_) => Err(DecodeError::ChecksumMismatch.into_de_error(self)),
+            Err(err) => Err(err.into_de_error(self)),
+        }
+    }
+
+    fn expecting_alphabet(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self.alphabet {
+            Alphabet::Hex => "a checksummed hex string",
+            Alphabet::Crockford => "a checksummed Crockford base32 string",
+        })
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for CheckedVisitor<[u8; N]> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.expecting_alphabet(formatter)?;
+        write!(formatter, " of {} bytes", N)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let len = self.payload_len(v)?;
+        if len != N {
+            return Err(E::invalid_length(len, &self));
+        }
+        let mut bytes = [0; N];
+        self.decode(v, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for CheckedVisitor<Vec<u8>> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.expecting_alphabet(formatter)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let mut bytes = alloc::vec![0; self.payload_len(v)?];
+        self.decode(v, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn deserialize_array<'de, D, const N: usize>(
+    deserializer: D,
+    format: Format,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(CheckedVisitor::<[u8; N]>::new(format))
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn deserialize_vec<'de, D>(deserializer: D, format: Format) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(CheckedVisitor::<Vec<u8>>::new(format))
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteBuf::into_vec)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::checked")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::checked")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+const HEX_FORMAT: Format = Format {
+    alphabet: Alphabet::Hex,
+    group: 0,
+};
+
+/// Serde `serialize_with` function to serialize bytes as checksummed hex.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer, HEX_FORMAT)
+}
+
+/// Serde `deserialize_with` function to deserialize checksummed hex.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer, HEX_FORMAT)
+}
+
+/// Checksummed hex in dash-separated groups of `GROUP` digits.
+///
+/// Use it as a module path:
+/// `#[serde(with = "serde_human_bytes::checked::Grouped::<4>")]`.
+pub struct Grouped<const GROUP: usize>;
+
+impl<const GROUP: usize> Grouped<GROUP> {
+    /// Serde `serialize_with` function to serialize bytes as grouped
+    /// checksummed hex.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        let format = Format {
+            alphabet: Alphabet::Hex,
+            group: GROUP,
+        };
+        Serialize::serialize(bytes, serializer, format)
+    }
+
+    /// Serde `deserialize_with` function to deserialize checksummed hex.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, HEX_FORMAT)
+    }
+}
+
+/// Checksummed Crockford base32.
+pub mod crockford {
+    use super::{Alphabet, Deserialize, Format, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    const FORMAT: Format = Format {
+        alphabet: Alphabet::Crockford,
+        group: 0,
+    };
+
+    /// Serde `serialize_with` function to serialize bytes as checksummed
+    /// Crockford base32.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(bytes, serializer, FORMAT)
+    }
+
+    /// Serde `deserialize_with` function to deserialize checksummed Crockford
+    /// base32.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, FORMAT)
+    }
+
+    /// Checksummed Crockford base32 in dash-separated groups of `GROUP`
+    /// characters.
+    ///
+    /// Use it as a module path:
+    /// `#[serde(with = "serde_human_bytes::checked::crockford::Grouped::<5>")]`.
+    pub struct Grouped<const GROUP: usize>;
+
+    impl<const GROUP: usize> Grouped<GROUP> {
+        /// Serde `serialize_with` function to serialize bytes as grouped
+        /// checksummed Crockford base32.
+        pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: ?Sized + Serialize,
+            S: Serializer,
+        {
+            let format = Format {
+                alphabet: Alphabet::Crockford,
+                group: GROUP,
+            };
+            Serialize::serialize(bytes, serializer, format)
+        }
+
+        /// Serde `deserialize_with` function to deserialize checksummed
+        /// Crockford base32.
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where
+            T: Deserialize<'de>,
+            D: Deserializer<'de>,
+        {
+            Deserialize::deserialize(deserializer, FORMAT)
+        }
+    }
+}
+
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(&**self, serializer, format)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Box<[u8]> {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Serialize for Cow<'a, [u8]> {
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, format)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer, format)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S, format: Format) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsChecked<T>(T, Format);
+
+        impl<T> serde::Serialize for AsChecked<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer, self.1)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsChecked(b, format)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_array(deserializer, format)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_array(deserializer, format).map(ByteArray::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, format)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, format).map(ByteBuf::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Box<[u8]> {
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, format).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, format: Format) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            format: Format,
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer, self.format).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor {
+            format,
+            out: PhantomData,
+        };
+        deserializer.deserialize_option(visitor)
+    }
+}
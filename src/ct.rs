@@ -0,0 +1,375 @@
+//! Constant-time hex and base64 for secret material.
+//!
+//! The `hex` and `base64` crates branch on and index lookup tables with the
+//! bytes they encode and decode, which can leak key material through timing
+//! and cache side channels. The modules here encode and decode with plain
+//! arithmetic instead: the time taken depends only on the length of the
+//! input, never on its contents.
+//!
+//! Select them per field with `#[serde(with = "serde_human_bytes::ct::hex")]`
+//! or `#[serde(with = "serde_human_bytes::ct::base64")]`. They produce and
+//! accept exactly the same text as the regular modules.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Key {
+//!     #[serde(with = "serde_human_bytes::ct::hex")]
+//!     private: [u8; 32],
+//!
+//!     #[serde(with = "serde_human_bytes::ct::base64")]
+//!     seed: Vec<u8>,
+//! }
+//! ```
+//!
+//! Malformed input is handed to the regular decoder to produce the usual
+//! error, so only rejecting input takes variable time.
+
+pub mod base64;
+pub mod hex;
+
+use core::fmt::{self, Display, Write};
+use core::str;
+
+use ::base64::DecodeError;
+use ::hex::FromHexError;
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Lowercase hex digit for the nibble `n`.
+fn encode_nibble(n: u8) -> u8 {
+    let n = i16::from(n);
+    // '0' + n, moved up past the gap between '9' and 'a' when n > 9. The low
+    // byte is taken with `to_le_bytes` rather than a cast, here and below.
+    (n + 0x30 + (((9 - n) >> 8) & 0x27)).to_le_bytes()[0]
+}
+
+/// Value of the hex digit `c`, or -1 if it is not one.
+fn decode_nibble(c: u8) -> i16 {
+    let c = i16::from(c);
+    let mut ret = -1;
+    // Each term is `c - base + 1` when `c` is in its range and 0 otherwise.
+    ret += (((0x2f - c) & (c - 0x3a)) >> 8) & (c - 47);
+    ret += (((0x40 - c) & (c - 0x47)) >> 8) & (c - 54);
+    ret += (((0x60 - c) & (c - 0x67)) >> 8) & (c - 86);
+    ret
+}
+
+/// Standard base64 digit for the 6-bit value `v`.
+fn encode_sextet(v: u8) -> u8 {
+    let v = i16::from(v);
+    // Offset from 'A', adjusted at each boundary of the alphabet.
+    let mut diff = 0x41;
+    diff += ((25 - v) >> 8) & 0x06;
+    diff -= ((51 - v) >> 8) & 0x4b;
+    diff -= ((61 - v) >> 8) & 0x0f;
+    diff += ((62 - v) >> 8) & 0x03;
+    (v + diff).to_le_bytes()[0]
+}
+
+/// Value of the standard base64 digit `c`, or -1 if it is not one.
+fn decode_sextet(c: u8) -> i16 {
+    let c = i16::from(c);
+    let mut ret = -1;
+    ret += (((0x40 - c) & (c - 0x5b)) >> 8) & (c - 64);
+    ret += (((0x60 - c) & (c - 0x7b)) >> 8) & (c - 70);
+    ret += (((0x2f - c) & (c - 0x3a)) >> 8) & (c + 5);
+    ret += (((0x2a - c) & (c - 0x2c)) >> 8) & 0x3f;
+    ret += (((0x2e - c) & (c - 0x30)) >> 8) & 0x40;
+    ret
+}
+
+/// Encode `bytes` through a stack buffer, `encode` filling it from up to 96
+/// input bytes at a time and returning the number of characters written.
+fn write_encoded<W, F>(out: &mut W, bytes: &[u8], encode: F) -> fmt::Result
+where
+    W: ?Sized + Write,
+    F: Fn(&[u8], &mut [u8; 192]) -> usize,
+{
+    let mut buf = [0; 192];
+    let mut result = Ok(());
+    for chunk in bytes.chunks(96) {
+        let len = encode(chunk, &mut buf);
+        result = match str::from_utf8(&buf[..len]) {
+            Ok(text) => out.write_str(text),
+            Err(_) => Err(fmt::Error),
+        };
+        if result.is_err() {
+            break;
+        }
+    }
+    #[cfg(feature = "secret")]
+    zeroize::Zeroize::zeroize(&mut buf);
+    result
+}
+
+/// Write `bytes` as lowercase hex in constant time.
+pub(crate) fn write_hex<W>(out: &mut W, bytes: &[u8]) -> fmt::Result
+where
+    W: ?Sized + Write,
+{
+    write_encoded(out, bytes, |chunk, buf| {
+        for (pair, &b) in buf.chunks_mut(2).zip(chunk) {
+            pair[0] = encode_nibble(b >> 4);
+            pair[1] = encode_nibble(b & 0xf);
+        }
+        chunk.len() * 2
+    })
+}
+
+/// Write `bytes` as padded standard base64 in constant time.
+pub(crate) fn write_base64<W>(out: &mut W, bytes: &[u8]) -> fmt::Result
+where
+    W: ?Sized + Write,
+{
+    write_encoded(out, bytes, |chunk, buf| {
+        let mut len = 0;
+        for group in chunk.chunks(3) {
+            let mut acc = 0u32;
+            for (i, &b) in group.iter().enumerate() {
+                acc |= u32::from(b) << (16 - 8 * i);
+            }
+            for i in 0..4 {
+                buf[len + i] = if i <= group.len() {
+                    encode_sextet((acc >> (18 - 6 * i)).to_le_bytes()[0] & 0x3f)
+                } else {
+                    b'='
+                };
+            }
+            len += 4;
+        }
+        len
+    })
+}
+
+/// Constant-time counterpart of `hex::decode_to_slice`, with the same errors.
+pub(crate) fn decode_hex_to_slice(input: &[u8], out: &mut [u8]) -> Result<(), FromHexError> {
+    if input.len() % 2 != 0 || input.len() / 2 != out.len() {
+        return ::hex::decode_to_slice(input, out);
+    }
+
+    let mut err = 0;
+    for (byte, pair) in out.iter_mut().zip(input.chunks(2)) {
+        let hi = decode_nibble(pair[0]);
+        let lo = decode_nibble(pair[1]);
+        err |= hi | lo;
+        *byte = ((hi << 4) | lo).to_le_bytes()[0];
+    }
+
+    if err < 0 {
+        return ::hex::decode_to_slice(input, out);
+    }
+    Ok(())
+}
+
+/// Constant-time counterpart of [`crate::base64::decode_to_slice`], with the
+/// same errors.
+pub(crate) fn decode_base64_to_slice(input: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+    // Where the padding sits is part of the length, which is not secret.
+    let pad = input
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&b| b == b'=')
+        .count();
+    let data = &input[..input.len() - pad];
+    let tail = data.len() % 4;
+    let well_formed = match pad {
+        0 => tail != 1,
+        1 => tail >= 2,
+        _ => tail == 2,
+    };
+    let len = data.len() / 4 * 3 + tail * 3 / 4;
+    if !well_formed || len > out.len() {
+        return crate::base64::decode_to_slice(input, out);
+    }
+
+    let mut err = 0;
+    for (quad, dst) in data.chunks(4).zip(out.chunks_mut(3)) {
+        let mut acc = 0u32;
+        for (i, &c) in quad.iter().enumerate() {
+            let v = decode_sextet(c);
+            err |= v;
+            acc |= u32::from(v.to_le_bytes()[0] & 0x3f) << (18 - 6 * i);
+        }
+        let n = quad.len() * 3 / 4;
+        for (i, byte) in dst[..n].iter_mut().enumerate() {
+            *byte = (acc >> (16 - 8 * i)).to_le_bytes()[0];
+        }
+        // Bits of a partial quad past its last whole byte must be zero.
+        let trailing = acc & (0x00ff_ffff >> (8 * n));
+        err |= -((trailing.wrapping_neg() >> 31) as i16);
+    }
+
+    if err < 0 {
+        return crate::base64::decode_to_slice(input, out);
+    }
+    Ok(len)
+}
+
+#[derive(Copy, Clone)]
+pub(crate) enum Codec {
+    Hex,
+    Base64,
+}
+
+struct CtDisplay<'a> {
+    bytes: &'a [u8],
+    codec: Codec,
+}
+
+impl<'a> Display for CtDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.codec {
+            Codec::Hex => write_hex(f, self.bytes),
+            Codec::Base64 => write_base64(f, self.bytes),
+        }
+    }
+}
+
+pub(crate) fn serialize_bytes<S>(
+    bytes: &[u8],
+    serializer: S,
+    codec: Codec,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&CtDisplay { bytes, codec })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct CtVecVisitor {
+    codec: Codec,
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for CtVecVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.codec {
+            Codec::Hex => formatter.write_str("a hex string"),
+            Codec::Base64 => formatter.write_str("a base64 string"),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        match self.codec {
+            Codec::Hex => {
+                if v.len() % 2 != 0 {
+                    return Err(crate::DecodeError::OddLength.into_de_error(&self));
+                }
+                let mut bytes = alloc::vec![0; v.len() / 2];
+                decode_hex_to_slice(v.as_bytes(), &mut bytes).map_err(|err| {
+                    crate::DecodeError::from_hex(err, v, bytes.len()).into_de_error(&self)
+                })?;
+                Ok(bytes)
+            }
+            Codec::Base64 => {
+                let mut bytes = alloc::vec![0; crate::base64::decoded_len(v.as_bytes())];
+                let len = decode_base64_to_slice(v.as_bytes(), &mut bytes)
+                    .map_err(|err| crate::DecodeError::from_base64(&err, v).into_de_error(&self))?;
+                bytes.truncate(len);
+                Ok(bytes)
+            }
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn deserialize_vec<'de, D>(deserializer: D, codec: Codec) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(CtVecVisitor { codec })
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(crate::ByteBuf::into_vec)
+    }
+}
+
+struct CtArrayVisitor<const N: usize> {
+    codec: Codec,
+}
+
+impl<'de, const N: usize> Visitor<'de> for CtArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.codec {
+            Codec::Hex => write!(formatter, "a hex string of {} bytes", N),
+            Codec::Base64 => write!(formatter, "a base64 string of {} bytes", N),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let mut bytes = [0; N];
+        match self.codec {
+            Codec::Hex => decode_hex_to_slice(v.as_bytes(), &mut bytes)
+                .map(|()| bytes)
+                .map_err(|err| crate::DecodeError::from_hex(err, v, N).into_de_error(&self)),
+            Codec::Base64 => {
+                let len = crate::base64::decoded_len(v.as_bytes());
+                if len != N {
+                    let err = crate::DecodeError::InvalidLength {
+                        expected: N,
+                        actual: len,
+                    };
+                    return Err(err.into_de_error(&self));
+                }
+                let err = match decode_base64_to_slice(v.as_bytes(), &mut bytes) {
+                    Ok(len) if len == N => return Ok(bytes),
+                    Ok(_) => DecodeError::InvalidLength,
+                    Err(err) => err,
+                };
+                Err(crate::DecodeError::from_base64(&err, v).into_de_error(&self))
+            }
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+pub(crate) fn deserialize_array<'de, D, const N: usize>(
+    deserializer: D,
+    codec: Codec,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(CtArrayVisitor::<N> { codec })
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(crate::ByteArray::into_array)
+    }
+}
@@ -0,0 +1,296 @@
+//! Constant-time base64, selected with
+//! `#[serde(with = "serde_human_bytes::ct::base64")]`.
+//!
+//! Produces and accepts the same padded standard base64 as
+//! [`serde_human_bytes::base64`](crate::base64), without branching on or
+//! indexing tables with the bytes.
+
+use super::Codec;
+use crate::{ByteArray, Bytes};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Error, Visitor};
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use crate::ByteBuf;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::ct::base64")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::ct::base64")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize bytes as base64 in constant
+/// time.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes from base64 in
+/// constant time.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(self, serializer, Codec::Base64)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(self, serializer, Codec::Base64)
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(self, serializer, Codec::Base64)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(&**self, serializer, Codec::Base64)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(self, serializer, Codec::Base64)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(self, serializer, Codec::Base64)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Box<[u8]> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_bytes(self, serializer, Codec::Base64)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl Serialize for crate::SecretBytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_as(serializer, crate::secret::Encoding::CtBase64)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<const N: usize> Serialize for crate::SecretArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_as(serializer, crate::secret::Encoding::CtBase64)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsBytes<T>(T);
+
+        impl<T> serde::Serialize for AsBytes<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsBytes(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_array(deserializer, Codec::Base64)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_array(deserializer, Codec::Base64).map(ByteArray::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_vec(deserializer, Codec::Base64)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_vec(deserializer, Codec::Base64).map(ByteBuf::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Box<[u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_vec(deserializer, Codec::Base64).map(Vec::into_boxed_slice)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<'de> Deserialize<'de> for crate::SecretBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::SecretBytes::deserialize_as(deserializer, crate::secret::Encoding::CtBase64)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<'de, const N: usize> Deserialize<'de> for crate::SecretArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::SecretArray::deserialize_as(deserializer, crate::secret::Encoding::CtBase64)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
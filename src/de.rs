@@ -1,27 +1,86 @@
+use crate::dynamic::Encoding;
+#[cfg(feature = "alloc")]
+use crate::error::ExpectedLen;
+use crate::seed;
 use crate::{ByteArray, Bytes};
-use core::convert::TryInto;
 use core::fmt;
 use core::marker::PhantomData;
-use serde::de::{Error, Visitor};
+use core::str;
+use serde::de::{Error, Unexpected, Visitor};
 use serde::Deserializer;
 
+#[cfg(feature = "alloc")]
 use crate::ByteBuf;
 
+#[cfg(feature = "alloc")]
 use core::cmp;
+#[cfg(feature = "alloc")]
+use core::convert::TryInto;
 
+#[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
 use serde::de::SeqAccess;
 
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+use crate::bytevec::{ByteVec, ByteVecVisitor, HexByteVecVisitor};
+
+#[cfg(feature = "alloc")]
 pub(crate) fn deserialize_hex<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = serde::Deserialize::deserialize(deserializer)?;
-    hex::decode(s).map_err(D::Error::custom)
+    seed::decode_vec(&s, Encoding::Hex).map_err(|err| err.into_de_error(&"a hex string"))
+}
+
+/// Decodes a hex string straight into a fixed-size array, without allocating.
+struct HexArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for HexArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a hex string of {} bytes", N)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        seed::decode_array(v, Encoding::Hex).map_err(|err| err.into_de_error(&self))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+pub(crate) fn deserialize_hex_array<'de, D, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(HexArrayVisitor::<N>)
 }
 
 /// Types that can be deserialized via `#[serde(with = "serde_human_bytes")]`.
@@ -38,7 +97,8 @@ impl<'de: 'a, 'a> Deserialize<'de> for &'a [u8] {
         D: Deserializer<'de>,
     {
         if deserializer.is_human_readable() {
-            // Not supported
+            // Hex cannot be borrowed. Fields holding text can borrow it with
+            // `serde_human_bytes::as_utf8` instead.
             Err(D::Error::custom(
                 "human readable mode is not supported for &[u8]",
             ))
@@ -49,6 +109,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for &'a [u8] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for Vec<u8> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -84,9 +145,7 @@ impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
         D: Deserializer<'de>,
     {
         if deserializer.is_human_readable() {
-            deserialize_hex(deserializer)?
-                .try_into()
-                .map_err(|_| D::Error::custom("invalid array length"))
+            deserialize_hex_array(deserializer)
         } else {
             let arr: ByteArray<N> = serde::Deserialize::deserialize(deserializer)?;
             Ok(*arr)
@@ -116,10 +175,7 @@ impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
         D: Deserializer<'de>,
     {
         if deserializer.is_human_readable() {
-            deserialize_hex(deserializer)?
-                .try_into()
-                .map(ByteArray::new)
-                .map_err(|_| D::Error::custom("invalid array length"))
+            deserialize_hex_array(deserializer).map(ByteArray::new)
         } else {
             // Via the serde::Deserialize impl for ByteArray.
             serde::Deserialize::deserialize(deserializer)
@@ -143,6 +199,7 @@ impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for &'a ByteArray<N> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for ByteBuf {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -157,6 +214,7 @@ impl<'de> Deserialize<'de> for ByteBuf {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -236,6 +294,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, [u8]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, Bytes> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -255,6 +314,7 @@ impl<'de: 'a, 'a> Deserialize<'de> for Cow<'a, Bytes> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for Box<[u8]> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -268,6 +328,7 @@ impl<'de> Deserialize<'de> for Box<[u8]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'de> Deserialize<'de> for Box<Bytes> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -284,6 +345,104 @@ impl<'de> Deserialize<'de> for Box<Bytes> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'de, const N: usize> Deserialize<'de> for Box<[u8; N]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Decode on the heap so that large arrays never touch the stack.
+        let bytes: Box<[u8]> = Deserialize::deserialize(deserializer)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Box<[u8]>| D::Error::invalid_length(bytes.len(), &ExpectedLen(N)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Rc<[u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Deserialize::deserialize(deserializer)?;
+        Ok(bytes.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Rc<Bytes> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(Bytes::from_rc)
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<'de> Deserialize<'de> for Arc<[u8]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Deserialize::deserialize(deserializer)?;
+        Ok(bytes.into())
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<'de> Deserialize<'de> for Arc<Bytes> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer).map(Bytes::from_arc)
+    }
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(no_target_has_atomic, target_has_atomic = "ptr")
+))]
+impl<'de, const N: usize> Deserialize<'de> for Arc<[u8; N]> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Arc<[u8]> = Deserialize::deserialize(deserializer)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Arc<[u8]>| D::Error::invalid_length(bytes.len(), &ExpectedLen(N)))
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<'de> Deserialize<'de> for crate::SecretBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::SecretBytes::deserialize_as(deserializer, crate::secret::Encoding::Hex)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<'de, const N: usize> Deserialize<'de> for crate::SecretArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::SecretArray::deserialize_as(deserializer, crate::secret::Encoding::Hex)
+    }
+}
+
 impl<'de, T> Deserialize<'de> for Option<T>
 where
     T: Deserialize<'de>,
@@ -326,3 +485,49 @@ where
         deserializer.deserialize_option(visitor)
     }
 }
+
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+fn deserialize_byte_vec<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: ByteVec,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(HexByteVecVisitor::new())
+    } else {
+        deserializer.deserialize_bytes(ByteVecVisitor::new())
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'de, A> Deserialize<'de> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array<Item = u8>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_byte_vec(deserializer)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'de, const CAP: usize> Deserialize<'de> for arrayvec::ArrayVec<u8, CAP> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_byte_vec(deserializer)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, const N: usize> Deserialize<'de> for heapless::Vec<u8, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_byte_vec(deserializer)
+    }
+}
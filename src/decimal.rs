@@ -0,0 +1,555 @@
+//! Bytes as unsigned decimal integers.
+//!
+//! Use `#[serde(with = "serde_human_bytes::decimal")]` to write a byte field
+//! in human-readable formats as the decimal digits of the big-endian unsigned
+//! integer it holds, or `serde_human_bytes::decimal::le` to read the bytes as
+//! little-endian. Compact formats get the bytes unchanged.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Balance {
+//!     // 1_000_000 is written as "1000000".
+//!     #[serde(with = "serde_human_bytes::decimal")]
+//!     amount: [u8; 16],
+//!
+//!     #[serde(with = "serde_human_bytes::decimal::le")]
+//!     fee: [u8; 8],
+//! }
+//! ```
+//!
+//! Fixed-size arrays are left-padded with zeros (right-padded for
+//! little-endian), and numbers too large for the array are rejected.
+//! Variable-size buffers deserialize to the shortest encoding of the number,
+//! so zero becomes an empty buffer and leading zero bytes do not round-trip.
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::ByteArray;
+
+#[cfg(feature = "alloc")]
+use crate::ByteBuf;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The byte order a decimal field is read in, picked by the module it is
+/// serialized with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// Most significant byte first, as with `serde_human_bytes::decimal`.
+    BigEndian,
+    /// Least significant byte first, as with `serde_human_bytes::decimal::le`.
+    LittleEndian,
+}
+
+/// The largest power of ten that fits in a `u64`, and its number of digits.
+const LIMB: u64 = 10_000_000_000_000_000_000;
+const LIMB_DIGITS: usize = 19;
+
+/// Divide the big-endian number `num` by `d` in place, returning the
+/// remainder.
+// The remainder stays below `d`, so the shifted remainder is below `256 * d`:
+// each quotient digit fits in a `u8` and the final remainder in a `u64`.
+#[allow(clippy::cast_possible_truncation)]
+fn div_rem(num: &mut [u8], d: u64) -> u64 {
+    let mut rem = 0u128;
+    for byte in num {
+        rem = (rem << 8) | u128::from(*byte);
+        *byte = (rem / u128::from(d)) as u8;
+        rem %= u128::from(d);
+    }
+    rem as u64
+}
+
+/// How many limbs `write_decimal` keeps on the stack at a time.
+const CHUNK: usize = 8;
+
+/// Write the big-endian number `num` in decimal. `scratch` must be as long as
+/// `num`.
+///
+/// The limbs come out least significant first but are written most
+/// significant first. Only `CHUNK` of them are kept at a time, so numbers of
+/// more than `CHUNK` limbs (64 bytes or more) are written in several passes,
+/// each dividing a fresh copy of `num` down to the limbs it writes.
+fn write_decimal(f: &mut fmt::Formatter, num: &[u8], scratch: &mut [u8]) -> fmt::Result {
+    // Limb `i` is kept at `limbs[i % CHUNK]`.
+    let mut limbs = [0; CHUNK];
+    scratch.copy_from_slice(num);
+    let mut count = 0;
+    while scratch.iter().any(|&b| b != 0) {
+        limbs[count % CHUNK] = div_rem(scratch, LIMB);
+        count += 1;
+    }
+    if count == 0 {
+        return f.write_str("0");
+    }
+
+    let mut hi = count;
+    loop {
+        let lo = hi.saturating_sub(CHUNK);
+        for i in (lo..hi).rev() {
+            if i == count - 1 {
+                write!(f, "{}", limbs[i % CHUNK])?;
+            } else {
+                write!(f, "{:019}", limbs[i % CHUNK])?;
+            }
+        }
+        if lo == 0 {
+            return Ok(());
+        }
+        hi = lo;
+        scratch.copy_from_slice(num);
+        for i in 0..hi {
+            let limb = div_rem(scratch, LIMB);
+            if i + CHUNK >= hi {
+                limbs[i % CHUNK] = limb;
+            }
+        }
+    }
+}
+
+struct DecimalArray<'a, const N: usize> {
+    bytes: &'a [u8; N],
+    order: Order,
+}
+
+impl<'a, const N: usize> Display for DecimalArray<'a, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut num = *self.bytes;
+        if self.order == Order::LittleEndian {
+            num.reverse();
+        }
+        write_decimal(f, &num, &mut [0; N])
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct DecimalSlice<'a> {
+    bytes: &'a [u8],
+    order: Order,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Display for DecimalSlice<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut num = self.bytes.to_vec();
+        if self.order == Order::LittleEndian {
+            num.reverse();
+        }
+        let mut scratch = alloc::vec![0; num.len()];
+        write_decimal(f, &num, &mut scratch)
+    }
+}
+
+/// Parse `v` as decimal digits, feeding each run of up to 19 digits to
+/// `mul_add` as a multiplier and an addend.
+fn parse_decimal<E, F>(v: &str, expected: &dyn serde::de::Expected, mut mul_add: F) -> Result<(), E>
+where
+    E: Error,
+    F: FnMut(u64, u64) -> bool,
+{
+    if v.is_empty() || !v.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(E::invalid_value(Unexpected::Str(v), expected));
+    }
+    for chunk in v.as_bytes().chunks(LIMB_DIGITS) {
+        let mut mul = 1;
+        let mut add = 0;
+        for &digit in chunk {
+            mul *= 10;
+            add = add * 10 + u64::from(digit - b'0');
+        }
+        if !mul_add(mul, add) {
+            return Err(E::invalid_value(Unexpected::Str(v), expected));
+        }
+    }
+    Ok(())
+}
+
+/// Compute `num * mul + add` in place for the little-endian `num`, returning
+/// the carry out of the most significant byte.
+fn mul_add(num: &mut [u8], mul: u64, add: u64) -> u128 {
+    let mut carry = u128::from(add);
+    for byte in num {
+        carry += u128::from(*byte) * u128::from(mul);
+        *byte = carry.to_le_bytes()[0];
+        carry >>= 8;
+    }
+    carry
+}
+
+struct DecimalArrayVisitor<const N: usize> {
+    order: Order,
+}
+
+impl<'de, const N: usize> Visitor<'de> for DecimalArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "an unsigned decimal integer that fits in {} bytes",
+            N
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        // Accumulate little-endian, then flip for big-endian.
+        let mut bytes = [0; N];
+        parse_decimal(v, &self, |mul, add| mul_add(&mut bytes, mul, add) == 0)?;
+        if self.order == Order::BigEndian {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn serialize_array<S, const N: usize>(
+    bytes: &[u8; N],
+    serializer: S,
+    order: Order,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&DecimalArray { bytes, order })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_array<'de, D, const N: usize>(
+    deserializer: D,
+    order: Order,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(DecimalArrayVisitor::<N> { order })
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct DecimalVecVisitor {
+    order: Order,
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for DecimalVecVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an unsigned decimal integer")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let mut bytes = Vec::new();
+        parse_decimal(v, &self, |mul, add| {
+            let mut carry = mul_add(&mut bytes, mul, add);
+            while carry != 0 {
+                bytes.push(carry.to_le_bytes()[0]);
+                carry >>= 8;
+            }
+            true
+        })?;
+        if self.order == Order::BigEndian {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn serialize_slice<S>(bytes: &[u8], serializer: S, order: Order) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&DecimalSlice { bytes, order })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn deserialize_vec<'de, D>(deserializer: D, order: Order) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(DecimalVecVisitor { order })
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteBuf::into_vec)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::decimal")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::decimal")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, order: Order) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize big-endian bytes as a
+/// decimal integer.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer, Order::BigEndian)
+}
+
+/// Serde `deserialize_with` function to deserialize a decimal integer into
+/// big-endian bytes.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer, Order::BigEndian)
+}
+
+/// Little-endian decimal integers, selected with
+/// `#[serde(with = "serde_human_bytes::decimal::le")]`.
+pub mod le {
+    use super::{Deserialize, Order, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize little-endian bytes as a
+    /// decimal integer.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(bytes, serializer, Order::LittleEndian)
+    }
+
+    /// Serde `deserialize_with` function to deserialize a decimal integer
+    /// into little-endian bytes.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, Order::LittleEndian)
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_array(self, serializer, order)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_array(self, serializer, order)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_slice(self, serializer, order)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_slice(self, serializer, order)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_slice(self, serializer, order)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer, order)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S, order: Order) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsDecimal<T>(T, Order);
+
+        impl<T> serde::Serialize for AsDecimal<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer, self.1)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsDecimal(b, order)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
+    fn deserialize<D>(deserializer: D, order: Order) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_array(deserializer, order)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D, order: Order) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_array(deserializer, order).map(ByteArray::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D, order: Order) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, order)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D, order: Order) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, order).map(ByteBuf::from)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, order: Order) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            order: Order,
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional decimal integer")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer, self.order).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor {
+            order,
+            out: PhantomData,
+        };
+        deserializer.deserialize_option(visitor)
+    }
+}
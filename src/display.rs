@@ -0,0 +1,92 @@
+//! Hex and base64 formatting shared by the `fmt` impls, the serializers and
+//! the `encode_*` methods.
+
+use core::fmt::{self, Debug, Display, Write};
+use core::str;
+
+use base64::display::Base64Display;
+
+const LOWER: &[u8; 16] = b"0123456789abcdef";
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Write `bytes` as hex through a small stack buffer, never allocating.
+pub(crate) fn write_hex<W>(out: &mut W, bytes: &[u8], upper: bool) -> fmt::Result
+where
+    W: ?Sized + Write,
+{
+    let table = if upper { UPPER } else { LOWER };
+    let mut buf = [0; 128];
+    let mut result = Ok(());
+    for chunk in bytes.chunks(buf.len() / 2) {
+        for (pair, &b) in buf.chunks_mut(2).zip(chunk) {
+            pair[0] = table[usize::from(b >> 4)];
+            pair[1] = table[usize::from(b & 0xf)];
+        }
+        result = match str::from_utf8(&buf[..chunk.len() * 2]) {
+            Ok(hex) => out.write_str(hex),
+            Err(_) => Err(fmt::Error),
+        };
+        if result.is_err() {
+            break;
+        }
+    }
+    // The buffer may hold an encoded secret.
+    #[cfg(feature = "secret")]
+    zeroize::Zeroize::zeroize(&mut buf);
+    result
+}
+
+/// Write `bytes` as padded standard base64, never allocating.
+pub(crate) fn write_base64<W>(out: &mut W, bytes: &[u8]) -> fmt::Result
+where
+    W: ?Sized + Write,
+{
+    write!(
+        out,
+        "{}",
+        Base64Display::with_config(bytes, base64::STANDARD)
+    )
+}
+
+/// Shared body of the `Display`, `LowerHex` and `UpperHex` impls: `{:#x}`
+/// prepends `0x`.
+pub(crate) fn fmt_hex(bytes: &[u8], f: &mut fmt::Formatter, upper: bool) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0x")?;
+    }
+    write_hex(f, bytes, upper)
+}
+
+/// `Debug` as `0x`-prefixed lowercase hex, returned by the `hex_debug`
+/// methods of the wrapper types.
+///
+/// The wrappers' own `Debug` impls print a list of numbers like `[u8]` does,
+/// which `{:x?}` shows in hex. `HexDebug` is for when a compact `0x414243`
+/// reads better, such as in a `Debug` impl of your own:
+///
+/// ```
+/// use serde_human_bytes::ByteBuf;
+///
+/// let buf = ByteBuf::from(b"ABC".to_vec());
+/// assert_eq!(format!("{:?}", buf), "[65, 66, 67]");
+/// assert_eq!(format!("{:x?}", buf), "[41, 42, 43]");
+/// assert_eq!(format!("{:?}", buf.hex_debug()), "0x414243");
+/// ```
+#[derive(Copy, Clone)]
+pub struct HexDebug<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Debug for HexDebug<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("0x")?;
+        write_hex(f, self.0, false)
+    }
+}
+
+/// Lowercase hex without any prefix, as emitted by the serializers.
+pub(crate) struct HexDisplay<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Display for HexDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.0, false)
+    }
+}
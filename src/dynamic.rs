@@ -0,0 +1,222 @@
+//! Bytes whose human-readable encoding is picked at runtime.
+//!
+//! Use `#[serde(with = "serde_human_bytes::dynamic")]` on any type supported
+//! by both `serde_human_bytes` and [`serde_human_bytes::base64`](crate::base64).
+//! The field is written as hex or base64 depending on the [`Encoding`] set for
+//! the current thread by [`with_encoding`], and as hex when none is set.
+//! Compact formats get the raw bytes either way.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_human_bytes::dynamic::{self, Encoding};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Blob {
+//!     #[serde(with = "serde_human_bytes::dynamic")]
+//!     data: Vec<u8>,
+//! }
+//!
+//! # fn to_string<T: Serialize>(value: &T) -> String { String::new() }
+//! # let blob = Blob { data: b"ABC".to_vec() };
+//! // `"414243"` by default, `"QUJD"` in here.
+//! # #[cfg(feature = "std")]
+//! let body = dynamic::with_encoding(Encoding::Base64, || to_string(&blob));
+//! ```
+//!
+//! The thread-local context requires the `std` feature. Without it, or to
+//! choose the encoding explicitly, wrap the bytes in [`Encoded`] to serialize
+//! them and deserialize them through a [`Seed`].
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::DeserializeSeed;
+use serde::{Deserializer, Serializer};
+
+/// The human-readable encoding of a `dynamic` field.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Lowercase hex, as with `#[serde(with = "serde_human_bytes")]`.
+    Hex,
+    /// Standard padded base64, as with
+    /// `#[serde(with = "serde_human_bytes::base64")]`.
+    Base64,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::Hex
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Encoding::Hex => "hex",
+            Encoding::Base64 => "base64",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CURRENT: core::cell::Cell<Option<Encoding>> = core::cell::Cell::new(None);
+}
+
+/// Run `f` with `encoding` as the encoding of `dynamic` fields on this
+/// thread, restoring the previous one afterwards, even if `f` panics.
+#[cfg(feature = "std")]
+pub fn with_encoding<F, R>(encoding: Encoding, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(Option<Encoding>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0;
+            CURRENT.with(|current| current.set(previous));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some(encoding))));
+    f()
+}
+
+/// The encoding of `dynamic` fields on this thread: the innermost
+/// [`with_encoding`], or hex outside of any.
+pub fn encoding() -> Encoding {
+    #[cfg(feature = "std")]
+    {
+        CURRENT.with(|current| current.get().unwrap_or_default())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        Encoding::Hex
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::dynamic")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, encoding: Encoding) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+impl<T> Serialize for T
+where
+    T: ?Sized + crate::Serialize + crate::base64::Serialize,
+{
+    fn serialize<S>(&self, serializer: S, encoding: Encoding) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match encoding {
+            Encoding::Hex => crate::Serialize::serialize(self, serializer),
+            Encoding::Base64 => crate::base64::Serialize::serialize(self, serializer),
+        }
+    }
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::dynamic")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, encoding: Encoding) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+impl<'de, T> Deserialize<'de> for T
+where
+    T: crate::Deserialize<'de> + crate::base64::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, encoding: Encoding) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match encoding {
+            Encoding::Hex => crate::Deserialize::deserialize(deserializer),
+            Encoding::Base64 => crate::base64::Deserialize::deserialize(deserializer),
+        }
+    }
+}
+
+/// Serde `serialize_with` function to serialize bytes in the current
+/// [`encoding`].
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer, encoding())
+}
+
+/// Serde `deserialize_with` function to deserialize bytes in the current
+/// [`encoding`].
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer, encoding())
+}
+
+/// Bytes that serialize in a given encoding, regardless of the thread's.
+pub struct Encoded<'a, T: ?Sized> {
+    bytes: &'a T,
+    encoding: Encoding,
+}
+
+impl<'a, T: ?Sized> Encoded<'a, T> {
+    /// Wrap `bytes` to be serialized in `encoding`.
+    pub fn new(bytes: &'a T, encoding: Encoding) -> Self {
+        Encoded { bytes, encoding }
+    }
+}
+
+impl<'a, T> serde::Serialize for Encoded<'a, T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Serialize::serialize(self.bytes, serializer, self.encoding)
+    }
+}
+
+/// A [`DeserializeSeed`] for bytes in a given encoding, regardless of the
+/// thread's.
+pub struct Seed<T> {
+    encoding: Encoding,
+    out: PhantomData<T>,
+}
+
+impl<T> Seed<T> {
+    /// Deserialize a `T` from `encoding`.
+    pub fn new(encoding: Encoding) -> Self {
+        Seed {
+            encoding,
+            out: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for Seed<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, self.encoding)
+    }
+}
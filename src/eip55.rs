@@ -0,0 +1,378 @@
+//! Ethereum addresses with EIP-55 checksum capitalization.
+//!
+//! Use `#[serde(with = "serde_human_bytes::eip55")]` on `[u8; 20]` or
+//! `ByteArray<20>` to write addresses in human-readable formats as
+//! `0x`-prefixed hex whose letters are capitalized according to
+//! [EIP-55](https://eips.ethereum.org/EIPS/eip-55), such as
+//! `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`. Compact formats get the 20
+//! bytes.
+//!
+//! Deserializing accepts all-lowercase and all-uppercase digits, which carry
+//! no checksum, and verifies the checksum of mixed-case input. The `0x`
+//! prefix is optional.
+//!
+//! This module requires the `eip55` feature, which bundles a Keccak-256
+//! implementation.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Transfer {
+//!     #[serde(with = "serde_human_bytes::eip55")]
+//!     to: [u8; 20],
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::{ByteArray, DecodeError};
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001,
+    0x0000_0000_0000_8082,
+    0x8000_0000_0000_808a,
+    0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a,
+    0x0000_0000_0000_0088,
+    0x0000_0000_8000_8009,
+    0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b,
+    0x8000_0000_0000_008b,
+    0x8000_0000_0000_8089,
+    0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002,
+    0x8000_0000_0000_0080,
+    0x0000_0000_0000_800a,
+    0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8080,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8008,
+];
+
+const ROTATIONS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The Keccak-f[1600] permutation, on lanes indexed by `x + 5 * y`.
+fn keccak_f(a: &mut [u64; 25]) {
+    for rc in &ROUND_CONSTANTS {
+        // θ
+        let mut c = [0; 5];
+        for x in 0..5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+        // ρ and π
+        let mut last = a[1];
+        for (&lane, &rotation) in LANES.iter().zip(&ROTATIONS) {
+            let next = a[lane];
+            a[lane] = last.rotate_left(rotation);
+            last = next;
+        }
+        // χ
+        for y in 0..5 {
+            let mut row = [0; 5];
+            row.copy_from_slice(&a[5 * y..5 * y + 5]);
+            for x in 0..5 {
+                a[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+        // ι
+        a[0] ^= rc;
+    }
+}
+
+/// Keccak-256 as used by Ethereum, with the original `0x01` padding rather
+/// than the `0x06` of SHA3-256.
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    const RATE: usize = 136;
+    let mut state = [0u64; 25];
+    let absorb = |state: &mut [u64; 25], block: &[u8; RATE]| {
+        for (lane, word) in state.iter_mut().zip(block.chunks(8)) {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(word);
+            *lane ^= u64::from_le_bytes(bytes);
+        }
+        keccak_f(state);
+    };
+
+    let mut chunks = data.chunks_exact(RATE);
+    let mut block = [0; RATE];
+    for chunk in &mut chunks {
+        block.copy_from_slice(chunk);
+        absorb(&mut state, &block);
+    }
+    let rest = chunks.remainder();
+    block = [0; RATE];
+    block[..rest.len()].copy_from_slice(rest);
+    block[rest.len()] ^= 0x01;
+    block[RATE - 1] ^= 0x80;
+    absorb(&mut state, &block);
+
+    let mut out = [0; 32];
+    for (word, lane) in out.chunks_mut(8).zip(&state) {
+        word.copy_from_slice(&lane.to_le_bytes());
+    }
+    out
+}
+
+/// The 40 hex digits of `bytes` with EIP-55 capitalization.
+fn checksummed(bytes: &[u8; 20]) -> [u8; 40] {
+    let mut digits = [0; 40];
+    hex::encode_to_slice(bytes, &mut digits).expect("40 digits for 20 bytes");
+    let hash = keccak256(&digits);
+    for (i, digit) in digits.iter_mut().enumerate() {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+        if nibble >= 8 {
+            digit.make_ascii_uppercase();
+        }
+    }
+    digits
+}
+
+struct Checksummed<'a>(&'a [u8; 20]);
+
+impl<'a> Display for Checksummed<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = checksummed(self.0);
+        f.write_str("0x")?;
+        f.write_str(str::from_utf8(&digits).map_err(|_| fmt::Error)?)
+    }
+}
+
+struct AddressVisitor;
+
+impl<'de> Visitor<'de> for AddressVisitor {
+    type Value = [u8; 20];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an EIP-55 address")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; 20], E>
+    where
+        E: Error,
+    {
+        let digits = v.strip_prefix("0x").unwrap_or(v);
+        let mut bytes = [0; 20];
+        if let Err(err) = hex::decode_to_slice(digits, &mut bytes) {
+            let err = DecodeError::from_hex(err, digits, 20);
+            return Err(err.offset_by(v.len() - digits.len()).into_de_error(&self));
+        }
+        let has_lower = digits.bytes().any(|b| b.is_ascii_lowercase());
+        let has_upper = digits.bytes().any(|b| b.is_ascii_uppercase());
+        if has_lower && has_upper && checksummed(&bytes)[..] != *digits.as_bytes() {
+            return Err(DecodeError::ChecksumMismatch.into_de_error(&self));
+        }
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; 20], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn serialize_address<S>(bytes: &[u8; 20], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Checksummed(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_address<'de, D>(deserializer: D) -> Result<[u8; 20], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(AddressVisitor)
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::eip55")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::eip55")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize an address with its EIP-55
+/// checksum.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize an address, verifying
+/// any EIP-55 checksum.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+impl Serialize for [u8; 20] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_address(self, serializer)
+    }
+}
+
+impl Serialize for ByteArray<20> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_address(self, serializer)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsBytes<T>(T);
+
+        impl<T> serde::Serialize for AsBytes<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsBytes(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for [u8; 20] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_address(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray<20> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_address(deserializer).map(ByteArray::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
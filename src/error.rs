@@ -0,0 +1,184 @@
+use core::fmt::{self, Display, Write};
+use core::str;
+
+use serde::de::{Error, Expected, Unexpected};
+
+/// Why a human-readable string could not be decoded into bytes.
+///
+/// The deserializers in this crate report these through
+/// [`invalid_value`](serde::de::Error::invalid_value) and
+/// [`invalid_length`](serde::de::Error::invalid_length), so that the format's
+/// own error points at the offending field:
+///
+/// ```text
+/// invalid value: invalid character 'x' at offset 5, expected a hex string of 3 bytes at line 1 column 14
+/// invalid length 2, expected a base64 string of 3 bytes at line 1 column 12
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// A character outside the alphabet of the encoding.
+    InvalidCharacter {
+        /// The character.
+        c: char,
+        /// Its byte offset in the input.
+        index: usize,
+    },
+    /// Hex with an odd number of digits.
+    OddLength,
+    /// Well-formed input that decodes to the wrong number of bytes.
+    InvalidLength {
+        /// The number of bytes required.
+        expected: usize,
+        /// The number of bytes the input decodes to.
+        actual: usize,
+    },
+    /// Base64 that is truncated, has misplaced `=` padding, or whose last
+    /// character has nonzero padding bits.
+    InvalidPadding {
+        /// The byte offset of the problem in the input.
+        index: usize,
+    },
+    /// Well-formed input whose checksum does not match its contents.
+    ChecksumMismatch,
+    /// Input that does not start with the prefix its type requires.
+    MissingPrefix {
+        /// The required prefix.
+        prefix: &'static str,
+    },
+}
+
+impl DecodeError {
+    /// Convert into the error type of a Serde deserializer, with `expected`
+    /// describing what the input should have been, usually the visitor.
+    ///
+    /// Wrong lengths become [`invalid_length`](serde::de::Error::invalid_length)
+    /// errors and everything else an
+    /// [`invalid_value`](serde::de::Error::invalid_value) error. Neither
+    /// repeats the input, which may be secret.
+    pub fn into_de_error<E>(self, expected: &dyn Expected) -> E
+    where
+        E: Error,
+    {
+        if let DecodeError::InvalidLength { actual, .. } = self {
+            return E::invalid_length(actual, expected);
+        }
+        let mut text = Text {
+            buf: [0; 64],
+            len: 0,
+        };
+        let _ = write!(text, "{}", self);
+        E::invalid_value(Unexpected::Other(text.as_str()), expected)
+    }
+
+    /// Shift the offset of the error by `offset` bytes, for input that was
+    /// decoded from a substring.
+    pub(crate) fn offset_by(self, offset: usize) -> DecodeError {
+        match self {
+            DecodeError::InvalidCharacter { c, index } => DecodeError::InvalidCharacter {
+                c,
+                index: index + offset,
+            },
+            DecodeError::InvalidPadding { index } => DecodeError::InvalidPadding {
+                index: index + offset,
+            },
+            err => err,
+        }
+    }
+
+    /// The character at `index` in `input`, falling back to `c` when `index`
+    /// is not at a character boundary.
+    fn character(input: &str, index: usize, c: char) -> DecodeError {
+        let c = input
+            .get(index..)
+            .and_then(|rest| rest.chars().next())
+            .unwrap_or(c);
+        DecodeError::InvalidCharacter { c, index }
+    }
+
+    /// Translate an error from decoding `input` as hex into `expected` bytes.
+    pub(crate) fn from_hex(err: hex::FromHexError, input: &str, expected: usize) -> DecodeError {
+        match err {
+            hex::FromHexError::InvalidHexCharacter { c, index } => {
+                DecodeError::character(input, index, c)
+            }
+            hex::FromHexError::OddLength => DecodeError::OddLength,
+            hex::FromHexError::InvalidStringLength => DecodeError::InvalidLength {
+                expected,
+                actual: input.len() / 2,
+            },
+        }
+    }
+
+    /// Translate an error from decoding `input` as base64. A length error
+    /// from the base64 crate itself means the input was truncated.
+    pub(crate) fn from_base64(err: &base64::DecodeError, input: &str) -> DecodeError {
+        match *err {
+            base64::DecodeError::InvalidByte(index, b'=')
+            | base64::DecodeError::InvalidLastSymbol(index, _) => {
+                DecodeError::InvalidPadding { index }
+            }
+            base64::DecodeError::InvalidByte(index, b) => {
+                DecodeError::character(input, index, char::from(b))
+            }
+            base64::DecodeError::InvalidLength => {
+                DecodeError::InvalidPadding { index: input.len() }
+            }
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::InvalidCharacter { c, index } => {
+                write!(f, "invalid character {:?} at offset {}", c, index)
+            }
+            DecodeError::OddLength => f.write_str("odd number of hex digits"),
+            DecodeError::InvalidLength { expected, actual } => {
+                write!(f, "invalid length {}, expected {} bytes", actual, expected)
+            }
+            DecodeError::InvalidPadding { index } => {
+                write!(f, "invalid padding at offset {}", index)
+            }
+            DecodeError::ChecksumMismatch => f.write_str("checksum mismatch"),
+            DecodeError::MissingPrefix { prefix } => write!(f, "missing {:?} prefix", prefix),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// The length of a fixed-size array, for errors outside any visitor.
+#[cfg(feature = "alloc")]
+pub(crate) struct ExpectedLen(pub(crate) usize);
+
+#[cfg(feature = "alloc")]
+impl Expected for ExpectedLen {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an array of {} bytes", self.0)
+    }
+}
+
+/// A short message formatted on the stack, for `Unexpected::Other`.
+struct Text {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl Text {
+    fn as_str(&self) -> &str {
+        str::from_utf8(&self.buf[..self.len]).unwrap_or("invalid input")
+    }
+}
+
+impl Write for Text {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let dst = self.buf.get_mut(self.len..end).ok_or(fmt::Error)?;
+        dst.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
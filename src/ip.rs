@@ -0,0 +1,314 @@
+//! IP addresses stored as `[u8; 4]` (IPv4) or `[u8; 16]` (IPv6).
+//!
+//! Use `#[serde(with = "serde_human_bytes::ip")]` to write them in
+//! human-readable formats the way `std::net` does: `10.0.0.1`, and IPv6 in
+//! the compressed form of RFC 5952, such as `::1`. Deserializing accepts
+//! whatever [`Ipv4Addr`] and [`Ipv6Addr`] parse, including uncompressed IPv6
+//! and embedded IPv4 (`::ffff:10.0.0.1`). Compact formats get the bytes.
+//!
+//! Using the module on an array of any other size is a compile error. This
+//! module requires the `std` feature.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Peer {
+//!     #[serde(with = "serde_human_bytes::ip")]
+//!     v4: [u8; 4],
+//!
+//!     #[serde(with = "serde_human_bytes::ip")]
+//!     v6: [u8; 16],
+//! }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::{self, FromStr};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::ByteArray;
+
+struct IpVisitor<A> {
+    expecting: &'static str,
+    addr: PhantomData<A>,
+}
+
+impl<'de, A> Visitor<'de> for IpVisitor<A>
+where
+    A: FromStr,
+{
+    type Value = A;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<A, E>
+    where
+        E: Error,
+    {
+        v.parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<A, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn serialize_v4<S>(bytes: [u8; 4], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Ipv4Addr::from(bytes))
+    } else {
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+fn serialize_v6<S>(bytes: &[u8; 16], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Ipv6Addr::from(*bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_v4<'de, D>(deserializer: D) -> Result<[u8; 4], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let visitor = IpVisitor::<Ipv4Addr> {
+            expecting: "an IPv4 address",
+            addr: PhantomData,
+        };
+        deserializer
+            .deserialize_str(visitor)
+            .map(|addr| addr.octets())
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+fn deserialize_v6<'de, D>(deserializer: D) -> Result<[u8; 16], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let visitor = IpVisitor::<Ipv6Addr> {
+            expecting: "an IPv6 address",
+            addr: PhantomData,
+        };
+        deserializer
+            .deserialize_str(visitor)
+            .map(|addr| addr.octets())
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::ip")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::ip")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize an IP address.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize an IP address.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+impl Serialize for [u8; 4] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_v4(*self, serializer)
+    }
+}
+
+impl Serialize for ByteArray<4> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_v4(**self, serializer)
+    }
+}
+
+impl Serialize for [u8; 16] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_v6(self, serializer)
+    }
+}
+
+impl Serialize for ByteArray<16> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_v6(self, serializer)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsBytes<T>(T);
+
+        impl<T> serde::Serialize for AsBytes<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsBytes(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for [u8; 4] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_v4(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray<4> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_v4(deserializer).map(ByteArray::new)
+    }
+}
+
+impl<'de> Deserialize<'de> for [u8; 16] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_v6(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray<16> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_v6(deserializer).map(ByteArray::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
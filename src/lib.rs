@@ -29,6 +29,29 @@
 //!     byte_array: [u8; 314],
 //! }
 //! ```
+//!
+//! # Optional features
+//!
+//! - `std` (default) — implies `alloc`, and enables the [`ip`] module and
+//!   [`dynamic::with_encoding`].
+//! - `alloc` — support `ByteBuf`, `Vec<u8>`, `Cow<[u8]>` and the boxed and
+//!   reference-counted byte types, `Arc` only on targets with pointer-width
+//!   atomics. Without it the crate is `no_std` without
+//!   `alloc`: `Bytes`, `ByteArray<N>`, `[u8; N]` and borrowed slices still
+//!   serialize, and `ByteArray<N>` and `[u8; N]` deserialize from
+//!   human-readable strings by decoding into the array in place.
+//! - `derive` — the [`human_bytes`] attribute, which applies this crate to
+//!   every byte field of a struct or enum. It needs Rust 1.71, for `syn`.
+//! - `bumpalo` — the [`arena`] module, which decodes borrowed byte fields
+//!   from human-readable formats into a `bumpalo` arena.
+//! - `eip55` — the [`eip55`] module for checksummed Ethereum addresses.
+//! - `sha2` — [`redact::sha256`], which logs byte fields as the start of
+//!   their SHA-256 digest.
+//! - `secret` — the [`SecretBytes`] and [`SecretArray`] types for key
+//!   material, which are wiped on drop and redacted in `Debug` output.
+//! - `smallvec`, `arrayvec`, `heapless` — support `SmallVec<[u8; N]>`,
+//!   `ArrayVec<u8, N>` and `heapless::Vec<u8, N>`. Deserializing more bytes
+//!   than a fixed-capacity container can hold is an error.
 
 #![doc(html_root_url = "https://docs.rs/serde_human_bytes/0.11.15")]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -42,14 +65,48 @@
     clippy::ptr_as_ptr
 )]
 
+#[cfg(feature = "bumpalo")]
+pub mod arena;
+pub mod as_utf8;
 pub mod base64;
 mod bytearray;
 mod bytes;
+#[cfg(any(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+mod bytevec;
+pub mod checked;
+pub mod ct;
 mod de;
+pub mod decimal;
+mod display;
+pub mod dynamic;
+#[cfg(feature = "eip55")]
+pub mod eip55;
+mod error;
+#[cfg(feature = "std")]
+pub mod ip;
+pub mod mac;
+pub mod mode;
+#[doc(hidden)]
+pub mod newtype;
+pub mod padded;
+pub mod quantity;
+pub mod redact;
+#[cfg(feature = "secret")]
+mod secret;
+pub mod seed;
 mod ser;
+pub mod uuid;
+pub mod via;
+pub mod wrapped;
 
+#[cfg(feature = "alloc")]
+pub mod bounded;
+#[cfg(feature = "alloc")]
 mod bytebuf;
+#[cfg(feature = "alloc")]
+pub mod pem;
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 use serde::{Deserializer, Serializer};
@@ -57,8 +114,18 @@ use serde::{Deserializer, Serializer};
 pub use crate::bytearray::ByteArray;
 pub use crate::bytes::Bytes;
 pub use crate::de::Deserialize;
+pub use crate::display::HexDebug;
+pub use crate::error::DecodeError;
+#[cfg(feature = "secret")]
+pub use crate::secret::{SecretArray, SecretBytes};
 pub use crate::ser::Serialize;
 
+#[cfg(feature = "derive")]
+pub use serde_human_bytes_derive::human_bytes;
+
+#[cfg(feature = "alloc")]
+pub use crate::bounded::BoundedByteBuf;
+#[cfg(feature = "alloc")]
 pub use crate::bytebuf::ByteBuf;
 
 /// Serde `serialize_with` function to serialize bytes efficiently.
@@ -0,0 +1,276 @@
+//! MAC addresses stored as `[u8; 6]`.
+//!
+//! Use `#[serde(with = "serde_human_bytes::mac")]` on `[u8; 6]` or
+//! `ByteArray<6>` to write them in human-readable formats as
+//! `aa:bb:cc:dd:ee:ff`. Deserializing also accepts uppercase digits, `-` as
+//! the separator, the dotted `aabb.ccdd.eeff` form and twelve bare digits.
+//! Compact formats get the 6 bytes.
+//!
+//! Using the module on an array of any other size is a compile error.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Interface {
+//!     #[serde(with = "serde_human_bytes::mac")]
+//!     address: [u8; 6],
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::display;
+use crate::ByteArray;
+
+struct Colons<'a>(&'a [u8; 6]);
+
+impl<'a> Display for Colons<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, byte) in self.0.chunks(1).enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            display::write_hex(f, byte, false)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode `text` as groups of `width` hex digits separated by `sep`.
+fn parse_groups(text: &[u8], width: usize, sep: u8) -> Option<[u8; 6]> {
+    let mut bytes = [0; 6];
+    let groups = text.split(|&b| b == sep);
+    let mut start = 0;
+    for group in groups {
+        if group.len() != width || start + width / 2 > 6 {
+            return None;
+        }
+        hex::decode_to_slice(group, &mut bytes[start..start + width / 2]).ok()?;
+        start += width / 2;
+    }
+    if start == 6 {
+        Some(bytes)
+    } else {
+        None
+    }
+}
+
+fn parse(s: &str) -> Option<[u8; 6]> {
+    let text = s.as_bytes();
+    match text.len() {
+        12 => parse_groups(text, 12, b':'),
+        14 => parse_groups(text, 4, b'.'),
+        17 => parse_groups(text, 2, text[2]).filter(|_| text[2] == b':' || text[2] == b'-'),
+        _ => None,
+    }
+}
+
+struct MacVisitor;
+
+impl<'de> Visitor<'de> for MacVisitor {
+    type Value = [u8; 6];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a MAC address")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; 6], E>
+    where
+        E: Error,
+    {
+        parse(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; 6], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn serialize_mac<S>(bytes: [u8; 6], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Colons(&bytes))
+    } else {
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+fn deserialize_mac<'de, D>(deserializer: D) -> Result<[u8; 6], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(MacVisitor)
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::mac")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::mac")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize a MAC address.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize a MAC address.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+impl Serialize for [u8; 6] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_mac(*self, serializer)
+    }
+}
+
+impl Serialize for ByteArray<6> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_mac(**self, serializer)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsBytes<T>(T);
+
+        impl<T> serde::Serialize for AsBytes<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsBytes(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for [u8; 6] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_mac(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray<6> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_mac(deserializer).map(ByteArray::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
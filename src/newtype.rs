@@ -0,0 +1,250 @@
+//! Support code for
+//! [`declare_byte_newtype!`](crate::declare_byte_newtype), which is not part
+//! of the public API.
+
+use core::fmt;
+use core::str;
+
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::display;
+use crate::dynamic::Encoding;
+use crate::{ByteArray, DecodeError};
+
+pub use serde;
+
+/// Declare fixed-size byte identifiers that encode the same way everywhere.
+///
+/// Each `struct Name([u8; N])` becomes a newtype around
+/// [`ByteArray<N>`](crate::ByteArray) that implements `Serialize` and
+/// `Deserialize`, `Display` and `FromStr`, `Debug`, `AsRef<[u8]>`,
+/// `From<[u8; N]>` and `Into<[u8; N]>`, and derives `Copy`, `Clone`,
+/// `Default`, `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash`.
+///
+/// The text form is lowercase hex unless an [`Encoding`] is given after `as`,
+/// optionally behind a `prefix` that parsing requires. Human-readable formats
+/// get the text form, compact formats the raw bytes. Parsing and
+/// deserializing accept exactly what is written and report a
+/// [`DecodeError`](crate::DecodeError) otherwise. `Debug` shows the type name
+/// and the bytes in hex, without the prefix, whatever the encoding.
+///
+/// ```
+/// serde_human_bytes::declare_byte_newtype! {
+///     /// The hash of a transaction.
+///     pub struct TxHash([u8; 32]) as Hex, prefix = "0x";
+///
+///     /// The identity of a peer.
+///     pub struct NodeId([u8; 20]) as Base64;
+///
+///     pub struct Nonce([u8; 12]);
+/// }
+///
+/// let hash: TxHash = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(hash.into_array()[0], 0x5c);
+/// assert!("5c504ed4".parse::<TxHash>().is_err());
+///
+/// let node = NodeId::new([0xff; 20]);
+/// assert_eq!(node.to_string(), "//////////////////////////8=");
+/// assert_eq!(format!("{:?}", node), format!("NodeId({})", "ff".repeat(20)));
+/// assert_eq!(format!("{:?}", Nonce::default()), "Nonce(000000000000000000000000)");
+/// ```
+#[macro_export]
+macro_rules! declare_byte_newtype {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident([u8; $len:expr]) $(as $encoding:ident $(, prefix = $prefix:literal)?)?;
+    )*) => {$(
+        $(#[$attr])*
+        #[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($crate::ByteArray<{ $len }>);
+
+        impl $name {
+            /// Wrap the given bytes.
+            pub const fn new(bytes: [u8; $len]) -> Self {
+                $name($crate::ByteArray::new(bytes))
+            }
+
+            /// Unwrap the underlying bytes.
+            pub const fn into_array(self) -> [u8; $len] {
+                self.0.into_array()
+            }
+        }
+
+        impl $crate::newtype::Newtype for $name {
+            const NAME: &'static str = ::core::stringify!($name);
+            const ENCODING: $crate::dynamic::Encoding = {
+                let encoding = $crate::dynamic::Encoding::Hex;
+                $(let encoding = $crate::dynamic::Encoding::$encoding;)?
+                encoding
+            };
+            const PREFIX: &'static str = {
+                let prefix = "";
+                $($(let prefix = $prefix;)?)?
+                prefix
+            };
+
+            fn as_bytes(&self) -> &[u8] {
+                &*self.0
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                $crate::newtype::fmt_debug(self, f)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                $crate::newtype::fmt(self, f)
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::DecodeError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $crate::newtype::parse::<$name, { $len }>(s).map($name::new)
+            }
+        }
+
+        impl ::core::convert::AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &*self.0
+            }
+        }
+
+        impl ::core::convert::From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                $name::new(bytes)
+            }
+        }
+
+        impl ::core::convert::From<$name> for [u8; $len] {
+            fn from(value: $name) -> Self {
+                value.into_array()
+            }
+        }
+
+        impl $crate::newtype::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::newtype::serde::Serializer,
+            {
+                $crate::newtype::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> $crate::newtype::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::newtype::serde::Deserializer<'de>,
+            {
+                $crate::newtype::deserialize::<$name, D, { $len }>(deserializer).map($name::new)
+            }
+        }
+    )*};
+}
+
+/// What [`declare_byte_newtype!`](crate::declare_byte_newtype) records about
+/// each type.
+pub trait Newtype: fmt::Display {
+    const NAME: &'static str;
+    const ENCODING: Encoding;
+    const PREFIX: &'static str;
+
+    fn as_bytes(&self) -> &[u8];
+}
+
+pub fn fmt<T>(value: &T, f: &mut fmt::Formatter) -> fmt::Result
+where
+    T: Newtype,
+{
+    f.write_str(T::PREFIX)?;
+    match T::ENCODING {
+        Encoding::Hex => display::write_hex(f, value.as_bytes(), false),
+        Encoding::Base64 => display::write_base64(f, value.as_bytes()),
+    }
+}
+
+pub fn fmt_debug<T>(value: &T, f: &mut fmt::Formatter) -> fmt::Result
+where
+    T: Newtype,
+{
+    write!(f, "{}(", T::NAME)?;
+    display::write_hex(f, value.as_bytes(), false)?;
+    f.write_str(")")
+}
+
+pub fn parse<T, const N: usize>(s: &str) -> Result<[u8; N], DecodeError>
+where
+    T: Newtype,
+{
+    let v = s
+        .strip_prefix(T::PREFIX)
+        .ok_or(DecodeError::MissingPrefix { prefix: T::PREFIX })?;
+    crate::seed::decode_array(v, T::ENCODING).map_err(|err| err.offset_by(T::PREFIX.len()))
+}
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Newtype,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(value)
+    } else {
+        serializer.serialize_bytes(value.as_bytes())
+    }
+}
+
+struct NewtypeVisitor<T, const N: usize> {
+    newtype: core::marker::PhantomData<T>,
+}
+
+impl<'de, T, const N: usize> Visitor<'de> for NewtypeVisitor<T, N>
+where
+    T: Newtype,
+{
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a {} string of {} bytes", T::ENCODING, N)?;
+        if !T::PREFIX.is_empty() {
+            write!(formatter, " prefixed with {:?}", T::PREFIX)?;
+        }
+        Ok(())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        parse::<T, N>(v).map_err(|err| err.into_de_error(&self))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+pub fn deserialize<'de, T, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+    T: Newtype,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(NewtypeVisitor::<T, N> {
+            newtype: core::marker::PhantomData,
+        })
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
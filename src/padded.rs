@@ -0,0 +1,374 @@
+//! Fixed-size arrays holding big-endian numbers, written without their
+//! leading zero bytes.
+//!
+//! Use `#[serde(with = "serde_human_bytes::padded")]` on `[u8; N]` or
+//! `ByteArray<N>`. Serializing strips leading zero bytes, so that
+//! `[0, 0, 1, 0]` is written as `"0100"`, and zero as `""`. Deserializing
+//! accepts anything up to `N` bytes long and left-pads it with zeros. Compact
+//! formats get the same trimmed bytes.
+//!
+//! For Ethereum `QUANTITY` values, which also drop leading zero nibbles and
+//! carry a `0x` prefix, see [`serde_human_bytes::quantity`](crate::quantity).
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Account {
+//!     #[serde(with = "serde_human_bytes::padded")]
+//!     balance: [u8; 32],
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::display;
+use crate::{ByteArray, DecodeError};
+
+/// `bytes` without its leading zero bytes.
+pub(crate) fn trim(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// Trimmed hex, optionally as an Ethereum quantity.
+struct Trimmed<'a> {
+    bytes: &'a [u8],
+    quantity: bool,
+}
+
+impl<'a> Display for Trimmed<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.quantity {
+            return display::write_hex(f, self.bytes, false);
+        }
+        match self.bytes.split_first() {
+            None => f.write_str("0x0"),
+            Some((first, rest)) => {
+                write!(f, "0x{:x}", first)?;
+                display::write_hex(f, rest, false)
+            }
+        }
+    }
+}
+
+pub(crate) fn serialize_trimmed<S>(
+    bytes: &[u8],
+    serializer: S,
+    quantity: bool,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let bytes = trim(bytes);
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Trimmed { bytes, quantity })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+struct PaddedVisitor<const N: usize> {
+    quantity: bool,
+    human_readable: bool,
+}
+
+impl<const N: usize> PaddedVisitor<N> {
+    /// Decode the hex `digits` at the end of `v` into the end of a zeroed
+    /// array. An odd number of digits is only allowed for quantities.
+    fn decode<E>(&self, v: &str, digits: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let len = (digits.len() + 1) / 2;
+        if len > N {
+            return Err(E::invalid_length(len, self));
+        }
+        // Report positions within `v`, counting any `0x` prefix.
+        let prefix = v.len() - digits.len();
+        let fail = |err| {
+            DecodeError::from_hex(err, digits, N)
+                .offset_by(prefix)
+                .into_de_error(self)
+        };
+        let mut bytes = [0; N];
+        let (head, tail) = digits.split_at(digits.len() % 2);
+        if !head.is_empty() {
+            if !self.quantity {
+                return Err(fail(hex::FromHexError::OddLength));
+            }
+            // Decode the lone leading digit as if it were zero-padded.
+            let pair = [b'0', head.as_bytes()[0]];
+            hex::decode_to_slice(pair, &mut bytes[N - len..][..1]).map_err(|_| {
+                fail(hex::FromHexError::InvalidHexCharacter {
+                    c: char::from(pair[1]),
+                    index: 0,
+                })
+            })?;
+        }
+        hex::decode_to_slice(tail, &mut bytes[N - tail.len() / 2..]).map_err(|err| match err {
+            hex::FromHexError::InvalidHexCharacter { c, index } => {
+                fail(hex::FromHexError::InvalidHexCharacter {
+                    c,
+                    index: index + head.len(),
+                })
+            }
+            err => fail(err),
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for PaddedVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if !self.human_readable {
+            write!(formatter, "a byte array of at most {} bytes", N)
+        } else if self.quantity {
+            write!(
+                formatter,
+                "a 0x-prefixed hex quantity of at most {} bytes",
+                N
+            )
+        } else {
+            write!(formatter, "a hex string of at most {} bytes", N)
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        if !self.human_readable {
+            return self.visit_bytes(v.as_bytes());
+        }
+        if !self.quantity {
+            return self.decode(v, v);
+        }
+        match v.strip_prefix("0x") {
+            Some("") | None => Err(E::invalid_value(Unexpected::Str(v), &self)),
+            Some(digits) => self.decode(v, digits),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        if self.human_readable {
+            let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+            return self.visit_str(s);
+        }
+        if v.len() > N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut bytes = [0; N];
+        bytes[N - v.len()..].copy_from_slice(v);
+        Ok(bytes)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<[u8; N], V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut bytes = [0; N];
+        let mut len = 0;
+        while let Some(b) = seq.next_element()? {
+            if len == N {
+                return Err(V::Error::invalid_length(len + 1, &self));
+            }
+            bytes[len] = b;
+            len += 1;
+        }
+        bytes.copy_within(..len, N - len);
+        for b in &mut bytes[..N - len] {
+            *b = 0;
+        }
+        Ok(bytes)
+    }
+}
+
+pub(crate) fn deserialize_padded<'de, D, const N: usize>(
+    deserializer: D,
+    quantity: bool,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(PaddedVisitor::<N> {
+            quantity,
+            human_readable: true,
+        })
+    } else {
+        deserializer.deserialize_bytes(PaddedVisitor::<N> {
+            quantity,
+            human_readable: false,
+        })
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::padded")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::padded")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize bytes without their leading
+/// zeros.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize up to `N` bytes into a
+/// left-padded array.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_trimmed(self, serializer, false)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_trimmed(&**self, serializer, false)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsBytes<T>(T);
+
+        impl<T> serde::Serialize for AsBytes<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsBytes(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_padded(deserializer, false)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_padded(deserializer, false).map(ByteArray::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
@@ -0,0 +1,568 @@
+//! PEM armor for DER-encoded certificates, requests and keys.
+//!
+//! Use `#[serde(with = "serde_human_bytes::pem::Pem::<L>")]`, where `L` is a
+//! [`Label`] such as [`Certificate`] or [`PrivateKey`], on a byte field
+//! holding DER. Human-readable formats get the bytes as a PEM block:
+//!
+//! ```text
+//! -----BEGIN CERTIFICATE-----
+//! MIIBszCCAVmgAwIBAgIUQ...
+//! -----END CERTIFICATE-----
+//! ```
+//!
+//! with the body base64-encoded in lines of 64 characters. Deserializing
+//! requires the label in both the `BEGIN` and `END` lines to match `L`, and
+//! tolerates whitespace around the block and within the body. A
+//! `Vec<Vec<u8>>` field is written as one block per element, one after the
+//! other, for certificate chains.
+//!
+//! Compact formats get the DER bytes.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use serde_human_bytes::pem::{Certificate, Label, Pem, PrivateKey};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Tls {
+//!     #[serde(with = "Pem::<Certificate>")]
+//!     chain: Vec<Vec<u8>>,
+//!
+//!     #[serde(with = "Pem::<PrivateKey>")]
+//!     key: Vec<u8>,
+//!
+//!     #[serde(with = "Pem::<TrustAnchor>")]
+//!     anchor: Option<Vec<u8>>,
+//! }
+//!
+//! enum TrustAnchor {}
+//!
+//! impl Label for TrustAnchor {
+//!     const LABEL: &'static str = "TRUST ANCHOR";
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use serde::de::{Error, Expected, SeqAccess, Unexpected, Visitor};
+use serde::ser::Serializer;
+use serde::Deserializer;
+
+use crate::wrapped::{self, Wrapped};
+use crate::{ByteBuf, Bytes};
+
+/// The type label of a PEM block, as in `-----BEGIN <LABEL>-----`.
+pub trait Label {
+    /// The label, such as `"CERTIFICATE"`.
+    const LABEL: &'static str;
+}
+
+/// The `CERTIFICATE` label, for X.509 certificates.
+pub enum Certificate {}
+
+impl Label for Certificate {
+    const LABEL: &'static str = "CERTIFICATE";
+}
+
+/// The `CERTIFICATE REQUEST` label, for PKCS #10 requests.
+pub enum CertificateRequest {}
+
+impl Label for CertificateRequest {
+    const LABEL: &'static str = "CERTIFICATE REQUEST";
+}
+
+/// The `PRIVATE KEY` label, for PKCS #8 private keys.
+pub enum PrivateKey {}
+
+impl Label for PrivateKey {
+    const LABEL: &'static str = "PRIVATE KEY";
+}
+
+/// The `PUBLIC KEY` label, for `SubjectPublicKeyInfo` public keys.
+pub enum PublicKey {}
+
+impl Label for PublicKey {
+    const LABEL: &'static str = "PUBLIC KEY";
+}
+
+const LINE_WIDTH: usize = 64;
+
+struct Block<'a> {
+    der: &'a [u8],
+    label: &'static str,
+}
+
+impl<'a> Display for Block<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "-----BEGIN {}-----", self.label)?;
+        if !self.der.is_empty() {
+            writeln!(f, "{}", Wrapped::base64(self.der, LINE_WIDTH))?;
+        }
+        writeln!(f, "-----END {}-----", self.label)
+    }
+}
+
+struct Chain<'a> {
+    ders: &'a [Vec<u8>],
+    label: &'static str,
+}
+
+impl<'a> Display for Chain<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for der in self.ders {
+            Display::fmt(
+                &Block {
+                    der,
+                    label: self.label,
+                },
+                f,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Why the armor around a PEM body could not be parsed. Errors in the body
+/// itself are [`DecodeError`](crate::DecodeError)s.
+enum PemError {
+    MissingBegin { index: usize },
+    MissingEnd { index: usize },
+    LabelMismatch { index: usize },
+    TrailingData { index: usize },
+}
+
+impl PemError {
+    fn offset_by(self, offset: usize) -> PemError {
+        match self {
+            PemError::MissingBegin { index } => PemError::MissingBegin {
+                index: index + offset,
+            },
+            PemError::MissingEnd { index } => PemError::MissingEnd {
+                index: index + offset,
+            },
+            PemError::LabelMismatch { index } => PemError::LabelMismatch {
+                index: index + offset,
+            },
+            PemError::TrailingData { index } => PemError::TrailingData {
+                index: index + offset,
+            },
+        }
+    }
+
+    /// Report as an invalid value, like
+    /// [`DecodeError::into_de_error`](crate::DecodeError::into_de_error).
+    fn into_de_error<E>(self, expected: &dyn Expected) -> E
+    where
+        E: Error,
+    {
+        E::invalid_value(Unexpected::Other(&self.to_string()), expected)
+    }
+}
+
+impl Display for PemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PemError::MissingBegin { index } => {
+                write!(f, "missing BEGIN line at offset {}", index)
+            }
+            PemError::MissingEnd { index } => write!(f, "missing END line at offset {}", index),
+            PemError::LabelMismatch { index } => {
+                write!(f, "wrong PEM label at offset {}", index)
+            }
+            PemError::TrailingData { index } => {
+                write!(f, "unexpected data at offset {}", index)
+            }
+        }
+    }
+}
+
+/// Split the first PEM block off `input`, returning its base64 body and the
+/// input after its `END` line. Errors are at offsets within `input`.
+fn parse_block<'a>(input: &'a str, label: &str) -> Result<(&'a str, &'a str), PemError> {
+    // Every slice below other than the body is a suffix of `input`.
+    let index = |rest: &str| input.len() - rest.len();
+
+    let begin = input.trim_start();
+    let rest = begin
+        .strip_prefix("-----BEGIN ")
+        .ok_or(PemError::MissingBegin {
+            index: index(begin),
+        })?;
+    let (found, after) = rest.split_once("-----").ok_or(PemError::MissingBegin {
+        index: index(begin),
+    })?;
+    if found != label {
+        return Err(PemError::LabelMismatch { index: index(rest) });
+    }
+    let (body, rest) = after
+        .split_once("-----END ")
+        .ok_or(PemError::MissingEnd { index: input.len() })?;
+    let after_label = rest
+        .strip_prefix(label)
+        .ok_or(PemError::LabelMismatch { index: index(rest) })?;
+    let rest = after_label
+        .strip_prefix("-----")
+        .ok_or(PemError::MissingEnd {
+            index: index(after_label),
+        })?;
+    Ok((body, rest))
+}
+
+struct PemVisitor<T> {
+    label: &'static str,
+    out: PhantomData<T>,
+}
+
+impl<T> PemVisitor<T> {
+    /// Decode the base64 `body` of a block within `v`, reporting errors at
+    /// their offset in `v`.
+    fn decode<E>(&self, v: &str, body: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+        Self: Expected,
+    {
+        wrapped::decode_base64_lines(body).map_err(|err| {
+            let offset = body.as_ptr() as usize - v.as_ptr() as usize;
+            err.offset_by(offset).into_de_error(self)
+        })
+    }
+}
+
+impl<'de> Visitor<'de> for PemVisitor<Vec<u8>> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a PEM {} block", self.label)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let (body, rest) = parse_block(v, self.label).map_err(|err| err.into_de_error(&self))?;
+        let rest = rest.trim_start();
+        if !rest.is_empty() {
+            let index = v.len() - rest.len();
+            return Err(PemError::TrailingData { index }.into_de_error(&self));
+        }
+        self.decode(v, body)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+impl<'de> Visitor<'de> for PemVisitor<Vec<Vec<u8>>> {
+    type Value = Vec<Vec<u8>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of PEM {} blocks", self.label)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<Vec<u8>>, E>
+    where
+        E: Error,
+    {
+        let mut ders = Vec::new();
+        let mut rest = v;
+        while !rest.trim().is_empty() {
+            let (body, after) = parse_block(rest, self.label)
+                .map_err(|err| err.offset_by(v.len() - rest.len()).into_de_error(&self))?;
+            ders.push(self.decode(v, body)?);
+            rest = after;
+        }
+        Ok(ders)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<Vec<u8>>, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Vec<Vec<u8>>, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut ders = Vec::new();
+        while let Some(der) = seq.next_element::<ByteBuf>()? {
+            ders.push(der.into_vec());
+        }
+        Ok(ders)
+    }
+}
+
+fn serialize_der<S>(der: &[u8], serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Block { der, label })
+    } else {
+        serializer.serialize_bytes(der)
+    }
+}
+
+fn deserialize_der<'de, D>(deserializer: D, label: &'static str) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(PemVisitor::<Vec<u8>> {
+            label,
+            out: PhantomData,
+        })
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteBuf::into_vec)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::pem::Pem::<L>")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::pem::Pem::<L>")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, label: &'static str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `with` path for DER fields written as PEM blocks labelled with
+/// `L::LABEL`: `#[serde(with = "serde_human_bytes::pem::Pem::<L>")]`.
+pub struct Pem<L> {
+    label: PhantomData<L>,
+}
+
+impl<L> Pem<L>
+where
+    L: Label,
+{
+    /// Serde `serialize_with` function to serialize DER as PEM.
+    pub fn serialize<T, S>(der: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(der, serializer, L::LABEL)
+    }
+
+    /// Serde `deserialize_with` function to deserialize DER from PEM.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, L::LABEL)
+    }
+}
+
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_der(self, serializer, label)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_der(self, serializer, label)
+    }
+}
+
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_der(self, serializer, label)
+    }
+}
+
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_der(self, serializer, label)
+    }
+}
+
+impl Serialize for Box<[u8]> {
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_der(self, serializer, label)
+    }
+}
+
+impl Serialize for Vec<Vec<u8>> {
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Chain { ders: self, label })
+        } else {
+            serializer.collect_seq(self.iter().map(|der| Bytes::new(der)))
+        }
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer, label)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S, label: &'static str) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsPem<T>(T, &'static str);
+
+        impl<T> serde::Serialize for AsPem<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer, self.1)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsPem(b, label)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D, label: &'static str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_der(deserializer, label)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D, label: &'static str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_der(deserializer, label).map(ByteBuf::from)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<[u8]> {
+    fn deserialize<D>(deserializer: D, label: &'static str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_der(deserializer, label).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<'de> Deserialize<'de> for Vec<Vec<u8>> {
+    fn deserialize<D>(deserializer: D, label: &'static str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let visitor = PemVisitor::<Vec<Vec<u8>>> {
+            label,
+            out: PhantomData,
+        };
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(visitor)
+        } else {
+            deserializer.deserialize_seq(visitor)
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, label: &'static str) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            label: &'static str,
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional PEM block")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer, self.label).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor {
+            label,
+            out: PhantomData,
+        };
+        deserializer.deserialize_option(visitor)
+    }
+}
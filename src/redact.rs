@@ -0,0 +1,351 @@
+//! One-way redacted output for logging.
+//!
+//! In human-readable formats these modules serialize a byte field as a short
+//! description instead of its contents, for dumping structs that hold keys or
+//! payloads into structured logs:
+//!
+//! - `#[serde(with = "serde_human_bytes::redact")]` — `"<32 bytes>"`
+//! - `#[serde(with = "serde_human_bytes::redact::Preview::<2, 2>")]` —
+//!   `"<32 bytes 0a1b…8e9f>"`, the first and last few bytes in hex, or only
+//!   the length for values too short to hide anything between them
+//! - `#[serde(with = "serde_human_bytes::redact::sha256")]` —
+//!   `"<32 bytes sha256:ab12cd34…>"`, the start of the SHA-256 digest of the
+//!   bytes (requires the `sha2` feature)
+//!
+//! Compact formats get the bytes unchanged.
+//!
+//! The redacted text cannot be turned back into bytes: deserializing from a
+//! human-readable format always fails. Deserializing from a compact format
+//! works like `#[serde(with = "serde_human_bytes")]`.
+//!
+//! ```
+//! # use serde_derive::Serialize;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Request {
+//!     #[serde(with = "serde_human_bytes::redact")]
+//!     payload: Vec<u8>,
+//!
+//!     #[serde(with = "serde_human_bytes::redact::Preview::<2, 2>")]
+//!     session: [u8; 16],
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+
+use crate::display;
+use crate::{ByteArray, Bytes};
+use serde::de::Error;
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use crate::ByteBuf;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// How much of a byte field is shown, picked by the module the field is
+/// serialized with.
+#[derive(Copy, Clone, Debug)]
+pub struct Redaction {
+    kind: Kind,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Kind {
+    Length,
+    Preview {
+        prefix: usize,
+        suffix: usize,
+    },
+    #[cfg(feature = "sha2")]
+    Sha256,
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::redact")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+struct Redacted<'a> {
+    bytes: &'a [u8],
+    redaction: Redaction,
+}
+
+impl<'a> Display for Redacted<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let len = self.bytes.len();
+        write!(f, "<{} byte{}", len, if len == 1 { "" } else { "s" })?;
+        match self.redaction.kind {
+            Kind::Length => {}
+            // Showing both ends of a short value would show all of it.
+            Kind::Preview { prefix, suffix } if len <= prefix + suffix => {}
+            Kind::Preview { prefix, suffix } => {
+                f.write_str(" ")?;
+                display::write_hex(f, &self.bytes[..prefix], false)?;
+                f.write_str("…")?;
+                display::write_hex(f, &self.bytes[len - suffix..], false)?;
+            }
+            #[cfg(feature = "sha2")]
+            Kind::Sha256 => {
+                use sha2::Digest;
+                let digest = sha2::Sha256::digest(self.bytes);
+                f.write_str(" sha256:")?;
+                display::write_hex(f, &digest[..4], false)?;
+                f.write_str("…")?;
+            }
+        }
+        f.write_str(">")
+    }
+}
+
+fn serialize_bytes<S>(bytes: &[u8], serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Redacted { bytes, redaction })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_compact<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: crate::Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        Err(D::Error::custom("redacted bytes cannot be deserialized"))
+    } else {
+        crate::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// Serde `serialize_with` function to serialize only the length of bytes.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    let redaction = Redaction { kind: Kind::Length };
+    Serialize::serialize(bytes, serializer, redaction)
+}
+
+/// Serde `deserialize_with` function that rejects human-readable input.
+///
+/// Compact input is deserialized as with `serde_human_bytes::deserialize`.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: crate::Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserialize_compact(deserializer)
+}
+
+/// Redaction showing the first `PREFIX` and last `SUFFIX` bytes in hex.
+///
+/// Use it as a module path:
+/// `#[serde(with = "serde_human_bytes::redact::Preview::<4, 0>")]`. Fields
+/// no longer than `PREFIX + SUFFIX` bytes show only their length, as with
+/// `serde_human_bytes::redact`.
+pub struct Preview<const PREFIX: usize, const SUFFIX: usize>;
+
+impl<const PREFIX: usize, const SUFFIX: usize> Preview<PREFIX, SUFFIX> {
+    /// Serde `serialize_with` function to serialize the length and the ends
+    /// of bytes.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        let redaction = Redaction {
+            kind: Kind::Preview {
+                prefix: PREFIX,
+                suffix: SUFFIX,
+            },
+        };
+        Serialize::serialize(bytes, serializer, redaction)
+    }
+
+    /// Serde `deserialize_with` function that rejects human-readable input.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: crate::Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserialize_compact(deserializer)
+    }
+}
+
+/// Redaction showing the start of the SHA-256 digest of the bytes.
+#[cfg(feature = "sha2")]
+pub mod sha256 {
+    use super::{Kind, Redaction, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    /// Serde `serialize_with` function to serialize the length and digest of
+    /// bytes.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        let redaction = Redaction { kind: Kind::Sha256 };
+        Serialize::serialize(bytes, serializer, redaction)
+    }
+
+    /// Serde `deserialize_with` function that rejects human-readable input.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: crate::Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_compact(deserializer)
+    }
+}
+
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(&**self, serializer, redaction)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Box<[u8]> {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Serialize for Cow<'a, [u8]> {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, redaction)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl Serialize for crate::SecretBytes {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.expose_secret(), serializer, redaction)
+    }
+}
+
+#[cfg(feature = "secret")]
+impl<const N: usize> Serialize for crate::SecretArray<N> {
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.expose_secret(), serializer, redaction)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer, redaction)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S, redaction: Redaction) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsRedacted<T>(T, Redaction);
+
+        impl<T> serde::Serialize for AsRedacted<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer, self.1)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsRedacted(b, redaction)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
@@ -0,0 +1,495 @@
+//! Byte containers for key material that wipe themselves when dropped.
+
+use core::cmp;
+use core::fmt::{self, Debug, Display};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Error as _, Serialize, Serializer};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::base64::{decode_to_slice, decoded_len};
+use crate::{ct, display, DecodeError};
+
+/// Text encoding used by the secret types in human-readable formats.
+#[derive(Copy, Clone)]
+pub(crate) enum Encoding {
+    Hex,
+    Base64,
+    CtHex,
+    CtBase64,
+}
+
+/// Wrapper around `Vec<u8>` for key material and other secrets.
+///
+/// Serializes like [`ByteBuf`](crate::ByteBuf), but its contents are wiped
+/// when it is dropped, including the temporary buffers used while encoding
+/// and decoding it. `Debug` and `Display` print only the length, and
+/// comparisons take time independent of the contents.
+///
+/// ```
+/// use serde_human_bytes::SecretBytes;
+///
+/// let key = SecretBytes::from(vec![0x42; 32]);
+/// assert_eq!(format!("{:?}", key), "[REDACTED; 32 bytes]");
+/// assert_eq!(key.expose_secret(), &[0x42; 32][..]);
+/// ```
+#[derive(Clone, Default)]
+pub struct SecretBytes {
+    bytes: Vec<u8>,
+}
+
+impl SecretBytes {
+    /// Construct a new, empty `SecretBytes`.
+    pub fn new() -> Self {
+        SecretBytes { bytes: Vec::new() }
+    }
+
+    /// Borrow the secret bytes.
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Mutably borrow the secret bytes.
+    pub fn expose_secret_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    /// Number of secret bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether there are no secret bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub(crate) fn serialize_as<S>(
+        &self,
+        serializer: S,
+        encoding: Encoding,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_secret(&self.bytes, serializer, encoding)
+    }
+
+    pub(crate) fn deserialize_as<'de, D>(
+        deserializer: D,
+        encoding: Encoding,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SecretBytesVisitor {
+                encoding: Some(encoding),
+            })
+        } else {
+            deserializer.deserialize_byte_buf(SecretBytesVisitor { encoding: None })
+        }
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        SecretBytes { bytes }
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretBytes {}
+
+impl Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[REDACTED; {} bytes]", self.bytes.len())
+    }
+}
+
+impl Display for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes.ct_eq(&other.bytes).into()
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl Serialize for SecretBytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_as(serializer, Encoding::Hex)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SecretBytes::deserialize_as(deserializer, Encoding::Hex)
+    }
+}
+
+/// Wrapper around `[u8; N]` for key material and other secrets.
+///
+/// Serializes like [`ByteArray<N>`](crate::ByteArray), but its contents are
+/// wiped when it is dropped, including the temporary buffers used while
+/// encoding and decoding it. `Debug` and `Display` print only the length, and
+/// comparisons take time independent of the contents.
+///
+/// Copies of the array made by moving a `SecretArray` are not tracked; box it
+/// if it must not be moved around on the stack.
+#[derive(Clone)]
+pub struct SecretArray<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> SecretArray<N> {
+    /// Wrap an existing array into a `SecretArray`.
+    pub const fn new(bytes: [u8; N]) -> Self {
+        SecretArray { bytes }
+    }
+
+    /// Borrow the secret bytes.
+    pub fn expose_secret(&self) -> &[u8; N] {
+        &self.bytes
+    }
+
+    /// Mutably borrow the secret bytes.
+    pub fn expose_secret_mut(&mut self) -> &mut [u8; N] {
+        &mut self.bytes
+    }
+
+    pub(crate) fn serialize_as<S>(
+        &self,
+        serializer: S,
+        encoding: Encoding,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_secret(&self.bytes, serializer, encoding)
+    }
+
+    pub(crate) fn deserialize_as<'de, D>(
+        deserializer: D,
+        encoding: Encoding,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SecretArrayVisitor::<N> {
+                encoding: Some(encoding),
+            })
+        } else {
+            deserializer.deserialize_bytes(SecretArrayVisitor::<N> { encoding: None })
+        }
+    }
+}
+
+impl<const N: usize> Default for SecretArray<N> {
+    fn default() -> Self {
+        SecretArray { bytes: [0; N] }
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for SecretArray<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        SecretArray { bytes }
+    }
+}
+
+impl<const N: usize> Drop for SecretArray<N> {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl<const N: usize> Zeroize for SecretArray<N> {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl<const N: usize> ZeroizeOnDrop for SecretArray<N> {}
+
+impl<const N: usize> Debug for SecretArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[REDACTED; {} bytes]", N)
+    }
+}
+
+impl<const N: usize> Display for SecretArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl<const N: usize> PartialEq for SecretArray<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes[..].ct_eq(&other.bytes[..]).into()
+    }
+}
+
+impl<const N: usize> Eq for SecretArray<N> {}
+
+impl<const N: usize> Serialize for SecretArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_as(serializer, Encoding::Hex)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for SecretArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SecretArray::deserialize_as(deserializer, Encoding::Hex)
+    }
+}
+
+fn serialize_secret<S>(bytes: &[u8], serializer: S, encoding: Encoding) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(bytes);
+    }
+
+    // Sized up front so that the string never reallocates and leaves a copy
+    // behind.
+    let capacity = match encoding {
+        Encoding::Hex | Encoding::CtHex => bytes.len() * 2,
+        Encoding::Base64 | Encoding::CtBase64 => (bytes.len() + 2) / 3 * 4,
+    };
+    let mut text = Zeroizing::new(String::with_capacity(capacity));
+    match encoding {
+        Encoding::Hex => display::write_hex(&mut *text, bytes, false),
+        Encoding::Base64 => display::write_base64(&mut *text, bytes),
+        Encoding::CtHex => ct::write_hex(&mut *text, bytes),
+        Encoding::CtBase64 => ct::write_base64(&mut *text, bytes),
+    }
+    .map_err(S::Error::custom)?;
+    serializer.serialize_str(&text)
+}
+
+/// Decode `v` into `out`, returning the number of bytes written.
+fn decode_into(v: &str, out: &mut [u8], encoding: Encoding) -> Result<usize, DecodeError> {
+    let len = out.len();
+    match encoding {
+        Encoding::Hex => hex::decode_to_slice(v, out)
+            .map(|()| len)
+            .map_err(|err| DecodeError::from_hex(err, v, len)),
+        Encoding::Base64 => {
+            decode_to_slice(v.as_bytes(), out).map_err(|err| DecodeError::from_base64(&err, v))
+        }
+        Encoding::CtHex => ct::decode_hex_to_slice(v.as_bytes(), out)
+            .map(|()| len)
+            .map_err(|err| DecodeError::from_hex(err, v, len)),
+        Encoding::CtBase64 => ct::decode_base64_to_slice(v.as_bytes(), out)
+            .map_err(|err| DecodeError::from_base64(&err, v)),
+    }
+}
+
+struct SecretBytesVisitor {
+    encoding: Option<Encoding>,
+}
+
+impl<'de> Visitor<'de> for SecretBytesVisitor {
+    type Value = SecretBytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.encoding {
+            Some(Encoding::Hex | Encoding::CtHex) => formatter.write_str("a hex string"),
+            Some(Encoding::Base64 | Encoding::CtBase64) => formatter.write_str("a base64 string"),
+            None => formatter.write_str("byte array"),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<SecretBytes, E>
+    where
+        E: Error,
+    {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => return self.visit_bytes(v.as_bytes()),
+        };
+        let len = match encoding {
+            Encoding::Hex | Encoding::CtHex if v.len() % 2 != 0 => {
+                return Err(DecodeError::OddLength.into_de_error(&self))
+            }
+            Encoding::Hex | Encoding::CtHex => v.len() / 2,
+            Encoding::Base64 | Encoding::CtBase64 => decoded_len(v.as_bytes()),
+        };
+        // Decode straight into the zeroizing container, so that partial
+        // output is wiped on error too.
+        let mut secret = SecretBytes::from(alloc::vec![0; len]);
+        let written =
+            decode_into(v, &mut secret.bytes, encoding).map_err(|err| err.into_de_error(&self))?;
+        secret.bytes.truncate(written);
+        Ok(secret)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<SecretBytes, E>
+    where
+        E: Error,
+    {
+        let v = Zeroizing::new(v);
+        self.visit_str(&v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<SecretBytes, E>
+    where
+        E: Error,
+    {
+        Ok(SecretBytes::from(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<SecretBytes, E>
+    where
+        E: Error,
+    {
+        Ok(SecretBytes::from(v))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<SecretBytes, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let len = cmp::min(visitor.size_hint().unwrap_or(0), 4096);
+        let mut secret = SecretBytes::from(Vec::with_capacity(len));
+
+        while let Some(b) = visitor.next_element()? {
+            if secret.bytes.len() == secret.bytes.capacity() {
+                // Grow by hand; letting `Vec` reallocate would free the old
+                // buffer without wiping it.
+                let mut grown = Vec::with_capacity(cmp::max(16, secret.bytes.len() * 2));
+                grown.extend_from_slice(&secret.bytes);
+                secret = SecretBytes::from(grown);
+            }
+            secret.bytes.push(b);
+        }
+
+        Ok(secret)
+    }
+}
+
+struct SecretArrayVisitor<const N: usize> {
+    encoding: Option<Encoding>,
+}
+
+impl<'de, const N: usize> Visitor<'de> for SecretArrayVisitor<N> {
+    type Value = SecretArray<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.encoding {
+            Some(Encoding::Hex | Encoding::CtHex) => {
+                write!(formatter, "a hex string of {} bytes", N)
+            }
+            Some(Encoding::Base64 | Encoding::CtBase64) => {
+                write!(formatter, "a base64 string of {} bytes", N)
+            }
+            None => write!(formatter, "a byte array of length {}", N),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<SecretArray<N>, E>
+    where
+        E: Error,
+    {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => return self.visit_bytes(v.as_bytes()),
+        };
+        let len = match encoding {
+            Encoding::Hex | Encoding::CtHex => v.len() / 2,
+            Encoding::Base64 | Encoding::CtBase64 => decoded_len(v.as_bytes()),
+        };
+        if len != N {
+            let err = DecodeError::InvalidLength {
+                expected: N,
+                actual: len,
+            };
+            return Err(err.into_de_error(&self));
+        }
+        let mut secret = SecretArray::default();
+        let written =
+            decode_into(v, &mut secret.bytes, encoding).map_err(|err| err.into_de_error(&self))?;
+        if written != N {
+            let err = DecodeError::InvalidPadding { index: v.len() };
+            return Err(err.into_de_error(&self));
+        }
+        Ok(secret)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<SecretArray<N>, E>
+    where
+        E: Error,
+    {
+        let v = Zeroizing::new(v);
+        self.visit_str(&v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<SecretArray<N>, E>
+    where
+        E: Error,
+    {
+        if v.len() != N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        let mut secret = SecretArray::default();
+        secret.bytes.copy_from_slice(v);
+        Ok(secret)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<SecretArray<N>, E>
+    where
+        E: Error,
+    {
+        let v = Zeroizing::new(v);
+        self.visit_bytes(&v)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<SecretArray<N>, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut secret = SecretArray::default();
+
+        for idx in 0..N {
+            secret.bytes[idx] = seq
+                .next_element()?
+                .ok_or_else(|| V::Error::invalid_length(idx, &self))?;
+        }
+
+        Ok(secret)
+    }
+}
@@ -0,0 +1,351 @@
+//! Deserializing bytes into storage the caller already owns.
+//!
+//! [`IntoBuf`] and [`IntoSlice`] are [`DeserializeSeed`]s that decode hex,
+//! base64 or compact bytes straight into an existing `Vec<u8>` or `[u8]`, so
+//! a loop handling many messages can reuse one allocation:
+//!
+//! ```
+//! use serde::de::DeserializeSeed;
+//! use serde_human_bytes::dynamic::Encoding;
+//! use serde_human_bytes::seed::IntoBuf;
+//!
+//! let mut buf = Vec::with_capacity(1024);
+//! for message in [&b"\x02AB"[..], b"\x02CD"].iter() {
+//!     let mut deserializer = bincode::Deserializer::from_slice(message, bincode::DefaultOptions::new());
+//!     IntoBuf::new(&mut buf, Encoding::Hex).deserialize(&mut deserializer)?;
+//!     assert_eq!(buf.len(), 2);
+//! }
+//! # Ok::<(), bincode::Error>(())
+//! ```
+//!
+//! The `Deserialize` impls of [`ByteBuf`](crate::ByteBuf) and
+//! [`ByteArray`](crate::ByteArray) also implement `deserialize_in_place` this
+//! way, which structs derived with serde's `deserialize_in_place` feature use
+//! to reuse the capacity of their fields.
+//!
+//! When deserializing fails, whatever the buffer holds is unspecified.
+
+use core::fmt;
+
+use serde::de::{DeserializeSeed, Error, SeqAccess, Unexpected, Visitor};
+use serde::Deserializer;
+
+use crate::dynamic::Encoding;
+use crate::DecodeError;
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The number of bytes `v` decodes to if it is well-formed.
+pub(crate) fn decoded_len(v: &str, encoding: Encoding) -> usize {
+    match encoding {
+        Encoding::Hex => v.len() / 2,
+        Encoding::Base64 => crate::base64::decoded_len(v.as_bytes()),
+    }
+}
+
+/// Decode `v` into the start of `out`, returning the number of bytes
+/// written.
+pub(crate) fn decode(v: &str, encoding: Encoding, out: &mut [u8]) -> Result<usize, DecodeError> {
+    let len = decoded_len(v, encoding);
+    if encoding == Encoding::Hex && v.len() % 2 != 0 {
+        return Err(DecodeError::OddLength);
+    }
+    if len > out.len() {
+        return Err(DecodeError::InvalidLength {
+            expected: out.len(),
+            actual: len,
+        });
+    }
+    match encoding {
+        Encoding::Hex => hex::decode_to_slice(v, &mut out[..len])
+            .map(|()| len)
+            .map_err(|err| DecodeError::from_hex(err, v, out.len())),
+        Encoding::Base64 => crate::base64::decode_to_slice(v.as_bytes(), out)
+            .map_err(|err| DecodeError::from_base64(&err, v)),
+    }
+}
+
+/// Decode `v` into exactly `N` bytes. A wrong length is reported before any
+/// invalid character, as the fixed-size array deserializers always have.
+pub(crate) fn decode_array<const N: usize>(
+    v: &str,
+    encoding: Encoding,
+) -> Result<[u8; N], DecodeError> {
+    let len = decoded_len(v, encoding);
+    let odd = encoding == Encoding::Hex && v.len() % 2 != 0;
+    if len != N && !odd {
+        return Err(DecodeError::InvalidLength {
+            expected: N,
+            actual: len,
+        });
+    }
+    let mut bytes = [0; N];
+    match decode(v, encoding, &mut bytes)? {
+        actual if actual == N => Ok(bytes),
+        actual => Err(DecodeError::InvalidLength {
+            expected: N,
+            actual,
+        }),
+    }
+}
+
+/// Decode `v` into a new vector of whatever length it holds.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_vec(v: &str, encoding: Encoding) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = vec![0; decoded_len(v, encoding)];
+    let len = decode(v, encoding, &mut bytes)?;
+    bytes.truncate(len);
+    Ok(bytes)
+}
+
+/// Deserialize bytes into a `Vec<u8>`, replacing its contents but keeping
+/// its allocation.
+#[cfg(feature = "alloc")]
+pub struct IntoBuf<'a> {
+    buf: &'a mut Vec<u8>,
+    encoding: Encoding,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> IntoBuf<'a> {
+    /// Decode human-readable input in `encoding` into `buf`.
+    pub fn new(buf: &'a mut Vec<u8>, encoding: Encoding) -> Self {
+        IntoBuf { buf, encoding }
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct BufVisitor<'a> {
+    buf: &'a mut Vec<u8>,
+    encoding: Encoding,
+    human_readable: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'de> Visitor<'de> for BufVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.human_readable {
+            write!(formatter, "a {} string", self.encoding)
+        } else {
+            formatter.write_str("byte array")
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<(), E>
+    where
+        E: Error,
+    {
+        if !self.human_readable {
+            return self.visit_bytes(v.as_bytes());
+        }
+        self.buf.clear();
+        self.buf.resize(decoded_len(v, self.encoding), 0);
+        match decode(v, self.encoding, self.buf) {
+            Ok(len) => {
+                self.buf.truncate(len);
+                Ok(())
+            }
+            Err(err) => Err(err.into_de_error(&self)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<(), E>
+    where
+        E: Error,
+    {
+        if self.human_readable {
+            let s = core::str::from_utf8(v)
+                .map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+            return self.visit_str(s);
+        }
+        self.buf.clear();
+        self.buf.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<(), V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        self.buf.clear();
+        while let Some(b) = seq.next_element()? {
+            self.buf.push(b);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn deserialize_into_buf<'de, D>(
+    deserializer: D,
+    buf: &mut Vec<u8>,
+    encoding: Encoding,
+) -> Result<(), D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BufVisitor {
+            buf,
+            encoding,
+            human_readable: true,
+        })
+    } else {
+        deserializer.deserialize_byte_buf(BufVisitor {
+            buf,
+            encoding,
+            human_readable: false,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'de> DeserializeSeed<'de> for IntoBuf<'a> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_into_buf(deserializer, self.buf, self.encoding)
+    }
+}
+
+/// Deserialize at most `slice.len()` bytes into the start of a slice,
+/// producing the number of bytes written.
+pub struct IntoSlice<'a> {
+    slice: &'a mut [u8],
+    encoding: Encoding,
+}
+
+impl<'a> IntoSlice<'a> {
+    /// Decode human-readable input in `encoding` into `slice`.
+    pub fn new(slice: &'a mut [u8], encoding: Encoding) -> Self {
+        IntoSlice { slice, encoding }
+    }
+}
+
+struct SliceVisitor<'a> {
+    slice: &'a mut [u8],
+    encoding: Encoding,
+    human_readable: bool,
+    /// Whether the input must fill the whole slice.
+    exact: bool,
+}
+
+impl<'a> SliceVisitor<'a> {
+    fn check_len<E>(&self, len: usize) -> Result<usize, E>
+    where
+        E: Error,
+    {
+        if len > self.slice.len() || self.exact && len != self.slice.len() {
+            return Err(E::invalid_length(len, self));
+        }
+        Ok(len)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SliceVisitor<'a> {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let bound = if self.exact { "" } else { "at most " };
+        if self.human_readable {
+            write!(
+                formatter,
+                "a {} string of {}{} bytes",
+                self.encoding,
+                bound,
+                self.slice.len()
+            )
+        } else {
+            write!(
+                formatter,
+                "a byte array of {}{} bytes",
+                bound,
+                self.slice.len()
+            )
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<usize, E>
+    where
+        E: Error,
+    {
+        if !self.human_readable {
+            return self.visit_bytes(v.as_bytes());
+        }
+        match decode(v, self.encoding, self.slice) {
+            Ok(len) => self.check_len(len),
+            Err(err) => Err(err.into_de_error(&self)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<usize, E>
+    where
+        E: Error,
+    {
+        if self.human_readable {
+            let s = core::str::from_utf8(v)
+                .map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+            return self.visit_str(s);
+        }
+        let len = self.check_len(v.len())?;
+        self.slice[..len].copy_from_slice(v);
+        Ok(len)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<usize, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut len = 0;
+        while let Some(b) = seq.next_element()? {
+            match self.slice.get_mut(len) {
+                Some(slot) => *slot = b,
+                None => return Err(V::Error::invalid_length(len + 1, &self)),
+            }
+            len += 1;
+        }
+        self.check_len(len)
+    }
+}
+
+pub(crate) fn deserialize_into_slice<'de, D>(
+    deserializer: D,
+    slice: &mut [u8],
+    encoding: Encoding,
+    exact: bool,
+) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let human_readable = deserializer.is_human_readable();
+    let visitor = SliceVisitor {
+        slice,
+        encoding,
+        human_readable,
+        exact,
+    };
+    if human_readable {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for IntoSlice<'a> {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<usize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_into_slice(deserializer, self.slice, self.encoding, false)
+    }
+}
@@ -0,0 +1,287 @@
+//! UUIDs stored as `[u8; 16]`.
+//!
+//! Use `#[serde(with = "serde_human_bytes::uuid")]` on `[u8; 16]` or
+//! `ByteArray<16>` to write them in human-readable formats in the hyphenated
+//! form, `550e8400-e29b-41d4-a716-446655440000`. Deserializing also accepts
+//! uppercase digits, the 32-digit simple form, and either form wrapped in
+//! braces or prefixed with `urn:uuid:`. Compact formats get the 16 bytes.
+//!
+//! Using the module on an array of any other size is a compile error:
+//!
+//! ```compile_fail
+//! # use serde_derive::Serialize;
+//! #[derive(Serialize)]
+//! struct Truncated {
+//!     #[serde(with = "serde_human_bytes::uuid")]
+//!     id: [u8; 15],
+//! }
+//! ```
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Order {
+//!     #[serde(with = "serde_human_bytes::uuid")]
+//!     id: [u8; 16],
+//! }
+//! ```
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str;
+
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::display;
+use crate::ByteArray;
+
+/// Offset in the hyphenated text and length in bytes of each group.
+const GROUPS: [(usize, usize); 5] = [(0, 4), (9, 2), (14, 2), (19, 2), (24, 6)];
+
+struct Hyphenated<'a>(&'a [u8; 16]);
+
+impl<'a> Display for Hyphenated<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut start = 0;
+        for (i, &(_, len)) in GROUPS.iter().enumerate() {
+            if i > 0 {
+                f.write_str("-")?;
+            }
+            display::write_hex(f, &self.0[start..start + len], false)?;
+            start += len;
+        }
+        Ok(())
+    }
+}
+
+fn parse(s: &str) -> Option<[u8; 16]> {
+    let s = s.strip_prefix("urn:uuid:").unwrap_or(s);
+    let s = match s.strip_prefix('{') {
+        Some(braced) => braced.strip_suffix('}')?,
+        None => s,
+    };
+    let mut bytes = [0; 16];
+    match s.len() {
+        32 => hex::decode_to_slice(s, &mut bytes).ok()?,
+        36 => {
+            let text = s.as_bytes();
+            let mut start = 0;
+            for (i, &(offset, len)) in GROUPS.iter().enumerate() {
+                if i > 0 && text[offset - 1] != b'-' {
+                    return None;
+                }
+                let digits = &text[offset..offset + len * 2];
+                hex::decode_to_slice(digits, &mut bytes[start..start + len]).ok()?;
+                start += len;
+            }
+        }
+        _ => return None,
+    }
+    Some(bytes)
+}
+
+struct UuidVisitor;
+
+impl<'de> Visitor<'de> for UuidVisitor {
+    type Value = [u8; 16];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a UUID")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; 16], E>
+    where
+        E: Error,
+    {
+        parse(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; 16], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn serialize_uuid<S>(bytes: &[u8; 16], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Hyphenated(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_uuid<'de, D>(deserializer: D) -> Result<[u8; 16], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(UuidVisitor)
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::uuid")]`.
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::uuid")]`.
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Serde `serialize_with` function to serialize a UUID in hyphenated form.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    Serialize::serialize(bytes, serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize a UUID.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer)
+}
+
+impl Serialize for [u8; 16] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_uuid(self, serializer)
+    }
+}
+
+impl Serialize for ByteArray<16> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_uuid(self, serializer)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsBytes<T>(T);
+
+        impl<T> serde::Serialize for AsBytes<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsBytes(b)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for [u8; 16] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_uuid(deserializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray<16> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_uuid(deserializer).map(ByteArray::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor { out: PhantomData };
+        deserializer.deserialize_option(visitor)
+    }
+}
@@ -0,0 +1,123 @@
+//! Byte containers that this crate knows nothing about.
+//!
+//! Use `#[serde(with = "serde_human_bytes::via")]` on a field of any type that
+//! implements `AsRef<[u8]>` and `TryFrom<Vec<u8>>`, such as a newtype around
+//! `Vec<u8>` or `Box<[u8]>`. It is serialized like its bytes would be with
+//! `#[serde(with = "serde_human_bytes")]`, and deserialized by converting the
+//! decoded `Vec<u8>`. An error from `try_from` becomes a deserialization error
+//! carrying its message.
+//!
+//! For types built from a fixed-size array, which need no allocation,
+//! `#[serde(with = "serde_human_bytes::via::Array::<N>")]` requires
+//! `From<[u8; N]>` instead.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//! use std::convert::TryFrom;
+//!
+//! struct PublicKey(Vec<u8>);
+//!
+//! impl AsRef<[u8]> for PublicKey {
+//!     fn as_ref(&self) -> &[u8] {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl TryFrom<Vec<u8>> for PublicKey {
+//!     type Error = &'static str;
+//!
+//!     fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+//!         if bytes.len() == 33 {
+//!             Ok(PublicKey(bytes))
+//!         } else {
+//!             Err("a public key is 33 bytes")
+//!         }
+//!     }
+//! }
+//!
+//! struct Nonce([u8; 12]);
+//!
+//! impl AsRef<[u8]> for Nonce {
+//!     fn as_ref(&self) -> &[u8] {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl From<[u8; 12]> for Nonce {
+//!     fn from(bytes: [u8; 12]) -> Self {
+//!         Nonce(bytes)
+//!     }
+//! }
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Handshake {
+//!     #[serde(with = "serde_human_bytes::via")]
+//!     key: PublicKey,
+//!
+//!     #[serde(with = "serde_human_bytes::via::Array::<12>")]
+//!     nonce: Nonce,
+//! }
+//! ```
+
+use serde::{Deserializer, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+#[cfg(feature = "alloc")]
+use core::fmt::Display;
+#[cfg(feature = "alloc")]
+use serde::de::Error;
+
+/// Serde `serialize_with` function to serialize the bytes of any
+/// `AsRef<[u8]>`.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + AsRef<[u8]>,
+    S: Serializer,
+{
+    crate::Serialize::serialize(value.as_ref(), serializer)
+}
+
+/// Serde `deserialize_with` function to deserialize bytes and convert them
+/// with `TryFrom<Vec<u8>>`.
+#[cfg(feature = "alloc")]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    T::Error: Display,
+    D: Deserializer<'de>,
+{
+    let bytes: Vec<u8> = crate::Deserialize::deserialize(deserializer)?;
+    T::try_from(bytes).map_err(D::Error::custom)
+}
+
+/// Conversion through a fixed-size array of `N` bytes.
+///
+/// Use it as a module path:
+/// `#[serde(with = "serde_human_bytes::via::Array::<32>")]`.
+pub struct Array<const N: usize>;
+
+impl<const N: usize> Array<N> {
+    /// Serde `serialize_with` function to serialize the bytes of any
+    /// `AsRef<[u8]>`.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + AsRef<[u8]>,
+        S: Serializer,
+    {
+        serialize(value, serializer)
+    }
+
+    /// Serde `deserialize_with` function to deserialize exactly `N` bytes
+    /// and convert them with `From<[u8; N]>`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<[u8; N]>,
+        D: Deserializer<'de>,
+    {
+        <[u8; N] as crate::Deserialize>::deserialize(deserializer).map(T::from)
+    }
+}
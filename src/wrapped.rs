@@ -0,0 +1,803 @@
+//! Line-wrapped output and whitespace-tolerant decoding for long blobs.
+//!
+//! Certificates and firmware images embedded in YAML or TOML are often
+//! line-wrapped or indented. These modules decode hex or base64 while
+//! skipping ASCII whitespace anywhere in the input:
+//!
+//! - `#[serde(with = "serde_human_bytes::wrapped::hex")]`
+//! - `#[serde(with = "serde_human_bytes::wrapped::base64")]`
+//! - `#[serde(with = "serde_human_bytes::wrapped::annotated")]` — hex that
+//!   may also contain `#` comments running to the end of the line, as in an
+//!   annotated hex dump
+//!
+//! Those serialize on a single line. To keep human-edited files
+//! diff-friendly, [`Hex<WIDTH>`] and [`Base64<WIDTH>`] instead break their
+//! output into lines of `WIDTH` characters separated by `\n`:
+//!
+//! - `#[serde(with = "serde_human_bytes::wrapped::Hex::<64>")]`
+//! - `#[serde(with = "serde_human_bytes::wrapped::Base64::<76>")]`
+//!
+//! Compact formats get the bytes unchanged.
+//!
+//! ```
+//! # use serde_derive::{Deserialize, Serialize};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Firmware {
+//!     #[serde(with = "serde_human_bytes::wrapped::Base64::<76>")]
+//!     image: Vec<u8>,
+//!
+//!     #[serde(with = "serde_human_bytes::wrapped::annotated")]
+//!     header: [u8; 16],
+//! }
+//! ```
+
+use core::fmt::{self, Display, Write};
+use core::marker::PhantomData;
+use core::str;
+
+use ::base64::display::Base64Display;
+use ::base64::DecodeError;
+use ::hex::FromHexError;
+use serde::de::{Error, Expected, Unexpected, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::display;
+use crate::{ByteArray, Bytes};
+
+#[cfg(feature = "alloc")]
+use crate::ByteBuf;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The encoding, line width and comment syntax of a wrapped field, picked by
+/// the module the field is serialized with.
+#[derive(Copy, Clone, Debug)]
+pub struct Layout {
+    encoding: Encoding,
+    width: usize,
+    comments: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Encoding {
+    Hex,
+    Base64,
+}
+
+/// Inserts a line break every `width` characters written through it.
+struct LineWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    width: usize,
+    column: usize,
+}
+
+impl<'a, 'b> Write for LineWriter<'a, 'b> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        if self.width == 0 {
+            return self.f.write_str(s);
+        }
+        while !s.is_empty() {
+            if self.column == self.width {
+                self.f.write_char('\n')?;
+                self.column = 0;
+            }
+            // Both encodings are ASCII, so any index is a char boundary.
+            let (line, rest) = s.split_at(usize::min(self.width - self.column, s.len()));
+            self.f.write_str(line)?;
+            self.column += line.len();
+            s = rest;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct Wrapped<'a> {
+    bytes: &'a [u8],
+    layout: Layout,
+}
+
+impl<'a> Wrapped<'a> {
+    /// Base64 in lines of `width` characters, as in a PEM body.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn base64(bytes: &'a [u8], width: usize) -> Self {
+        let layout = Layout {
+            encoding: Encoding::Base64,
+            width,
+            comments: false,
+        };
+        Wrapped { bytes, layout }
+    }
+}
+
+impl<'a> Display for Wrapped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = LineWriter {
+            f,
+            width: self.layout.width,
+            column: 0,
+        };
+        match self.layout.encoding {
+            Encoding::Hex => display::write_hex(&mut out, self.bytes, false),
+            Encoding::Base64 => write!(
+                out,
+                "{}",
+                Base64Display::with_config(self.bytes, ::base64::STANDARD)
+            ),
+        }
+    }
+}
+
+fn serialize_bytes<S>(bytes: &[u8], serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Wrapped { bytes, layout })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+impl Layout {
+    /// The digits of `input` and their positions in it, skipping whitespace
+    /// and comments.
+    fn digits<'a>(self, input: &'a str) -> impl Iterator<Item = (usize, u8)> + 'a {
+        let comments = self.comments;
+        let mut in_comment = false;
+        input.bytes().enumerate().filter(move |&(_, b)| {
+            if in_comment {
+                in_comment = b != b'\n';
+                false
+            } else if comments && b == b'#' {
+                in_comment = true;
+                false
+            } else {
+                !b.is_ascii_whitespace()
+            }
+        })
+    }
+
+    /// The number of bytes `input` decodes to, if it is well-formed.
+    fn decoded_len(self, input: &str) -> Result<usize, crate::DecodeError> {
+        let mut count = 0;
+        let mut padding = 0;
+        for (_, b) in self.digits(input) {
+            count += 1;
+            padding = if b == b'=' { padding + 1 } else { 0 };
+        }
+        match self.encoding {
+            Encoding::Hex if count % 2 != 0 => Err(crate::DecodeError::OddLength),
+            Encoding::Hex => Ok(count / 2),
+            Encoding::Base64 => {
+                let len = count - usize::min(padding, 2);
+                Ok(len / 4 * 3 + len % 4 * 3 / 4)
+            }
+        }
+    }
+
+    /// Decode `input` into `out`, which must be exactly `decoded_len` long.
+    fn decode(self, input: &str, out: &mut [u8]) -> Result<(), crate::DecodeError> {
+        let len = out.len();
+        match self.encoding {
+            Encoding::Hex => decode_hex(self.digits(input), out)
+                .map_err(|err| crate::DecodeError::from_hex(err, input, len)),
+            Encoding::Base64 => decode_base64(self.digits(input), out)
+                .map_err(|err| crate::DecodeError::from_base64(&err, input)),
+        }
+    }
+}
+
+fn decode_hex<I>(mut digits: I, out: &mut [u8]) -> Result<(), FromHexError>
+where
+    I: Iterator<Item = (usize, u8)>,
+{
+    let value = |(index, c): (usize, u8)| match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(FromHexError::InvalidHexCharacter {
+            c: char::from(c),
+            index,
+        }),
+    };
+    for b in out {
+        match (digits.next(), digits.next()) {
+            (Some(hi), Some(lo)) => *b = value(hi)? << 4 | value(lo)?,
+            _ => return Err(FromHexError::OddLength),
+        }
+    }
+    Ok(())
+}
+
+fn decode_base64<I>(digits: I, out: &mut [u8]) -> Result<(), DecodeError>
+where
+    I: Iterator<Item = (usize, u8)>,
+{
+    let mut quad = [0; 4];
+    let mut positions = [0; 4];
+    let mut len = 0;
+    let mut written = 0;
+    let mut finished = false;
+
+    let mut flush = |quad: &[u8], positions: &[usize], written: &mut usize| {
+        let mut buf = [0; 3];
+        let n =
+            ::base64::decode_config_slice(quad, ::base64::STANDARD, &mut buf).map_err(|err| {
+                match err {
+                    DecodeError::InvalidByte(i, b) => DecodeError::InvalidByte(positions[i], b),
+                    DecodeError::InvalidLastSymbol(i, b) => {
+                        DecodeError::InvalidLastSymbol(positions[i], b)
+                    }
+                    DecodeError::InvalidLength => DecodeError::InvalidLength,
+                }
+            })?;
+        let dst = out
+            .get_mut(*written..*written + n)
+            .ok_or(DecodeError::InvalidLength)?;
+        dst.copy_from_slice(&buf[..n]);
+        *written += n;
+        Ok(n)
+    };
+
+    for (position, b) in digits {
+        if finished {
+            // Only padding may follow the quad that held the final bytes.
+            return Err(DecodeError::InvalidByte(position, b));
+        }
+        quad[len] = b;
+        positions[len] = position;
+        len += 1;
+        if len == 4 {
+            finished = quad.contains(&b'=');
+            flush(&quad, &positions, &mut written)?;
+            len = 0;
+        }
+    }
+    if len > 0 {
+        flush(&quad[..len], &positions[..len], &mut written)?;
+    }
+    if written != out.len() {
+        return Err(DecodeError::InvalidLength);
+    }
+    Ok(())
+}
+
+/// Decode base64 split across lines, as in a PEM body.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_base64_lines(input: &str) -> Result<Vec<u8>, crate::DecodeError> {
+    let layout = Layout {
+        encoding: Encoding::Base64,
+        width: 0,
+        comments: false,
+    };
+    let mut bytes = alloc::vec![0; layout.decoded_len(input)?];
+    layout.decode(input, &mut bytes)?;
+    Ok(bytes)
+}
+
+struct WrappedVisitor<T> {
+    layout: Layout,
+    out: PhantomData<T>,
+}
+
+impl<T> WrappedVisitor<T> {
+    fn new(layout: Layout) -> Self {
+        WrappedVisitor {
+            layout,
+            out: PhantomData,
+        }
+    }
+
+    fn expecting_encoding(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self.layout.encoding {
+            Encoding::Hex => "a hex string",
+            Encoding::Base64 => "a base64 string",
+        })
+    }
+
+    fn decode<E>(&self, v: &str, out: &mut [u8]) -> Result<(), E>
+    where
+        E: Error,
+        Self: Expected,
+    {
+        self.layout
+            .decode(v, out)
+            .map_err(|err| err.into_de_error(self))
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for WrappedVisitor<[u8; N]> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.expecting_encoding(formatter)?;
+        write!(formatter, " of {} bytes", N)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let len = self
+            .layout
+            .decoded_len(v)
+            .map_err(|err| err.into_de_error(&self))?;
+        if len != N {
+            return Err(E::invalid_length(len, &self));
+        }
+        let mut bytes = [0; N];
+        self.decode(v, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<[u8; N], E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for WrappedVisitor<Vec<u8>> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.expecting_encoding(formatter)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let len = self
+            .layout
+            .decoded_len(v)
+            .map_err(|err| err.into_de_error(&self))?;
+        let mut bytes = alloc::vec![0; len];
+        self.decode(v, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Vec<u8>, E>
+    where
+        E: Error,
+    {
+        let s = str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+}
+
+fn deserialize_array<'de, D, const N: usize>(
+    deserializer: D,
+    layout: Layout,
+) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(WrappedVisitor::<[u8; N]>::new(layout))
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteArray::into_array)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn deserialize_vec<'de, D>(deserializer: D, layout: Layout) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(WrappedVisitor::<Vec<u8>>::new(layout))
+    } else {
+        serde::Deserialize::deserialize(deserializer).map(ByteBuf::into_vec)
+    }
+}
+
+/// Types that can be serialized via
+/// `#[serde(with = "serde_human_bytes::wrapped::hex")]` and the other
+/// modules in [`wrapped`](self).
+pub trait Serialize {
+    #[allow(missing_docs)]
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer;
+}
+
+/// Types that can be deserialized via
+/// `#[serde(with = "serde_human_bytes::wrapped::hex")]` and the other
+/// modules in [`wrapped`](self).
+pub trait Deserialize<'de>: Sized {
+    #[allow(missing_docs)]
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Hex on one line, decoded skipping whitespace.
+pub mod hex {
+    use super::{Deserialize, Encoding, Layout, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    const LAYOUT: Layout = Layout {
+        encoding: Encoding::Hex,
+        width: 0,
+        comments: false,
+    };
+
+    /// Serde `serialize_with` function to serialize bytes as hex.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(bytes, serializer, LAYOUT)
+    }
+
+    /// Serde `deserialize_with` function to deserialize hex, skipping whitespace.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, LAYOUT)
+    }
+}
+
+/// Base64 on one line, decoded skipping whitespace.
+pub mod base64 {
+    use super::{Deserialize, Encoding, Layout, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    const LAYOUT: Layout = Layout {
+        encoding: Encoding::Base64,
+        width: 0,
+        comments: false,
+    };
+
+    /// Serde `serialize_with` function to serialize bytes as base64.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(bytes, serializer, LAYOUT)
+    }
+
+    /// Serde `deserialize_with` function to deserialize base64, skipping whitespace.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, LAYOUT)
+    }
+}
+
+/// Hex on one line, decoded skipping whitespace and `#` comments.
+pub mod annotated {
+    use super::{Deserialize, Encoding, Layout, Serialize};
+    use serde::{Deserializer, Serializer};
+
+    const LAYOUT: Layout = Layout {
+        encoding: Encoding::Hex,
+        width: 0,
+        comments: true,
+    };
+
+    /// Serde `serialize_with` function to serialize bytes as hex.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        Serialize::serialize(bytes, serializer, LAYOUT)
+    }
+
+    /// Serde `deserialize_with` function to deserialize hex, skipping whitespace and
+    /// `#` comments.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer, LAYOUT)
+    }
+}
+
+/// Hex in lines of `WIDTH` characters.
+///
+/// Use it as a module path:
+/// `#[serde(with = "serde_human_bytes::wrapped::Hex::<64>")]`.
+/// Deserializing skips whitespace.
+pub struct Hex<const WIDTH: usize>;
+
+impl<const WIDTH: usize> Hex<WIDTH> {
+    /// Serde `serialize_with` function to serialize bytes as wrapped
+    /// hex.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        let layout = Layout {
+            encoding: Encoding::Hex,
+            width: WIDTH,
+            comments: false,
+        };
+        Serialize::serialize(bytes, serializer, layout)
+    }
+
+    /// Serde `deserialize_with` function to deserialize wrapped
+    /// hex.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let layout = Layout {
+            encoding: Encoding::Hex,
+            width: WIDTH,
+            comments: false,
+        };
+        Deserialize::deserialize(deserializer, layout)
+    }
+}
+
+/// Base64 in lines of `WIDTH` characters.
+///
+/// Use it as a module path:
+/// `#[serde(with = "serde_human_bytes::wrapped::Base64::<76>")]`.
+/// Deserializing skips whitespace.
+pub struct Base64<const WIDTH: usize>;
+
+impl<const WIDTH: usize> Base64<WIDTH> {
+    /// Serde `serialize_with` function to serialize bytes as wrapped
+    /// base64.
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        let layout = Layout {
+            encoding: Encoding::Base64,
+            width: WIDTH,
+            comments: false,
+        };
+        Serialize::serialize(bytes, serializer, layout)
+    }
+
+    /// Serde `deserialize_with` function to deserialize wrapped
+    /// base64.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let layout = Layout {
+            encoding: Encoding::Base64,
+            width: WIDTH,
+            comments: false,
+        };
+        Deserialize::deserialize(deserializer, layout)
+    }
+}
+
+impl Serialize for [u8] {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+impl<const N: usize> Serialize for ByteArray<N> {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(&**self, serializer, layout)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Vec<u8> {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for ByteBuf {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Box<[u8]> {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Serialize for Cow<'a, [u8]> {
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self, serializer, layout)
+    }
+}
+
+impl<T> Serialize for &T
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer, layout)
+    }
+}
+
+impl<T> Serialize for Option<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S, layout: Layout) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct AsWrapped<T>(T, Layout);
+
+        impl<T> serde::Serialize for AsWrapped<T>
+        where
+            T: Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.0.serialize(serializer, self.1)
+            }
+        }
+
+        match self {
+            Some(b) => serializer.serialize_some(&AsWrapped(b, layout)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for [u8; N] {
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_array(deserializer, layout)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteArray<N> {
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_array(deserializer, layout).map(ByteArray::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Vec<u8> {
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, layout)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, layout).map(ByteBuf::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Box<[u8]> {
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_vec(deserializer, layout).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Option<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D, layout: Layout) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor<T> {
+            layout: Layout,
+            out: PhantomData<T>,
+        }
+
+        impl<'de, T> Visitor<'de> for BytesVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("optional byte array")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer, self.layout).map(Some)
+            }
+        }
+
+        let visitor = BytesVisitor {
+            layout,
+            out: PhantomData,
+        };
+        deserializer.deserialize_option(visitor)
+    }
+}
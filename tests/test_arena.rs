@@ -0,0 +1,108 @@
+#![cfg(feature = "bumpalo")]
+#![allow(clippy::ref_option_ref)]
+
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::arena::Arena;
+use serde_human_bytes::mode::Readable;
+use serde_human_bytes::Bytes;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Record<'a> {
+    #[serde(with = "serde_human_bytes::arena", borrow)]
+    key: &'a [u8],
+
+    #[serde(with = "serde_human_bytes::arena::base64", borrow)]
+    value: Option<&'a Bytes>,
+}
+
+fn record() -> Record<'static> {
+    Record {
+        key: b"key",
+        value: Some(Bytes::new(b"value")),
+    }
+}
+
+/// Bincode made to look human-readable, so that byte fields are written as
+/// strings.
+fn readable_bincode(record: &Record) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let mut serializer = bincode::Serializer::new(&mut buf, bincode::DefaultOptions::new());
+    record.serialize(Readable::new(&mut serializer)).unwrap();
+    buf
+}
+
+#[test]
+fn test_arena_readable() {
+    let buf = readable_bincode(&record());
+    assert_eq!(buf, b"\x066b6579\x01\x08dmFsdWU=");
+
+    let bump = Bump::new();
+    let mut deserializer = bincode::Deserializer::from_slice(&buf, bincode::DefaultOptions::new());
+    let arena = Arena::new(Readable::new(&mut deserializer), &bump);
+    let decoded = Record::deserialize(arena).unwrap();
+    assert_eq!(decoded, record());
+    assert!(bump.allocated_bytes() > 0);
+}
+
+#[test]
+fn test_arena_compact_borrows_input() {
+    let buf = bincode::serialize(&record()).unwrap();
+    let decoded: Record = bincode::deserialize(&buf).unwrap();
+    assert_eq!(decoded, record());
+    let range = buf.as_ptr_range();
+    assert!(range.contains(&decoded.key.as_ptr()));
+}
+
+#[test]
+fn test_arena_errors() {
+    let buf = readable_bincode(&record());
+    let mut deserializer = bincode::Deserializer::from_slice(&buf, bincode::DefaultOptions::new());
+    let err = Record::deserialize(Readable::new(&mut deserializer)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "human-readable byte fields must be deserialized through serde_human_bytes::arena::Arena",
+    );
+
+    let bad = Record {
+        key: b"\x01",
+        ..record()
+    };
+    let mut buf = readable_bincode(&bad);
+    buf[2] = b'x';
+    let bump = Bump::new();
+    let mut deserializer = bincode::Deserializer::from_slice(&buf, bincode::DefaultOptions::new());
+    let err = Record::deserialize(Arena::new(Readable::new(&mut deserializer), &bump)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: invalid character 'x' at offset 1, expected a hex string",
+    );
+}
+
+#[test]
+fn test_arena_json() {
+    let bump = Bump::new();
+    let mut deserializer =
+        serde_json::Deserializer::from_str(r#"{"key":"6b6579","value":"dmFsdWU="}"#);
+    let decoded = Record::deserialize(Arena::new(&mut deserializer, &bump)).unwrap();
+    assert_eq!(decoded, record());
+
+    // Escapes make serde_json hand over a transient string instead of one
+    // borrowed from the input.
+    let json = r#"{"key":"\u0036b6579","value":"dmFsdWU\u003d"}"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let decoded = Record::deserialize(Arena::new(&mut deserializer, &bump)).unwrap();
+    assert_eq!(decoded, record());
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Key<'a>(#[serde(with = "serde_human_bytes::arena", borrow)] &'a [u8]);
+
+#[test]
+fn test_arena_json_newtype() {
+    let bump = Bump::new();
+    let mut deserializer = serde_json::Deserializer::from_str(r#"["6b6579","6b"]"#);
+    let decoded = Vec::<Key>::deserialize(Arena::new(&mut deserializer, &bump)).unwrap();
+    assert_eq!(decoded, [Key(b"key"), Key(b"k")]);
+}
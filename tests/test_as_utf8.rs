@@ -0,0 +1,120 @@
+use serde::de::value::{self, BorrowedStrDeserializer, StrDeserializer};
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens_error, assert_ser_tokens, assert_ser_tokens_error, assert_tokens, Configure,
+    Token,
+};
+use std::borrow::Cow;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Frame<'a> {
+    #[serde(with = "serde_human_bytes::as_utf8", borrow)]
+    text: &'a [u8],
+
+    #[serde(with = "serde_human_bytes::as_utf8")]
+    tag: [u8; 2],
+
+    #[serde(with = "serde_human_bytes::as_utf8::lossy")]
+    name: Option<Vec<u8>>,
+}
+
+fn frame(name: &[u8]) -> Frame<'static> {
+    Frame {
+        text: b"hi",
+        tag: *b"ok",
+        name: Some(name.to_vec()),
+    }
+}
+
+fn tokens(name: &'static str, text: Token) -> [Token; 9] {
+    [
+        Token::Struct {
+            name: "Frame",
+            len: 3,
+        },
+        Token::Str("text"),
+        text,
+        Token::Str("tag"),
+        Token::Str("ok"),
+        Token::Str("name"),
+        Token::Some,
+        Token::Str(name),
+        Token::StructEnd,
+    ]
+}
+
+#[test]
+fn test_as_utf8_readable() {
+    assert_tokens(
+        &frame(b"ab").readable(),
+        &tokens("ab", Token::BorrowedStr("hi")),
+    );
+    assert_ser_tokens(
+        &frame(b"a\xffb").readable(),
+        &tokens("a\u{fffd}b", Token::Str("hi")),
+    );
+}
+
+#[test]
+fn test_as_utf8_compact() {
+    assert_tokens(
+        &frame(b"a\xffb").compact(),
+        &[
+            Token::Struct {
+                name: "Frame",
+                len: 3,
+            },
+            Token::Str("text"),
+            Token::BorrowedBytes(b"hi"),
+            Token::Str("tag"),
+            Token::Bytes(b"ok"),
+            Token::Str("name"),
+            Token::Some,
+            Token::Bytes(b"a\xffb"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_as_utf8_borrowed() {
+    let de = BorrowedStrDeserializer::<value::Error>::new("hi");
+    let cow: Cow<[u8]> = serde_human_bytes::as_utf8::deserialize(de).unwrap();
+    assert!(matches!(cow, Cow::Borrowed(b"hi")));
+
+    let de = StrDeserializer::<value::Error>::new("hi");
+    let cow: Cow<[u8]> = serde_human_bytes::as_utf8::deserialize(de).unwrap();
+    assert!(matches!(cow, Cow::Owned(_)));
+
+    let de = StrDeserializer::<value::Error>::new("hi");
+    let err = serde_human_bytes::as_utf8::deserialize::<&[u8], _>(de).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: string \"hi\", expected a borrowed UTF-8 string"
+    );
+}
+
+#[test]
+fn test_as_utf8_errors() {
+    #[derive(Serialize, Debug)]
+    struct Strict(#[serde(with = "serde_human_bytes::as_utf8")] Vec<u8>);
+
+    assert_ser_tokens_error(
+        &Strict(b"a\xffb".to_vec()).readable(),
+        &[Token::NewtypeStruct { name: "Strict" }],
+        "invalid UTF-8 at offset 1",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Frame>>(
+        &[
+            Token::Struct {
+                name: "Frame",
+                len: 3,
+            },
+            Token::Str("text"),
+            Token::BorrowedStr("hi"),
+            Token::Str("tag"),
+            Token::Str("abc"),
+        ],
+        "invalid length 3, expected a UTF-8 string of 2 bytes",
+    );
+}
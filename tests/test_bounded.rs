@@ -0,0 +1,108 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::BoundedByteBuf;
+use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+use std::convert::TryFrom;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Upload {
+    #[serde(with = "serde_human_bytes::bounded::Bounded::<1, 3>")]
+    body: Vec<u8>,
+
+    nonce: BoundedByteBuf<2, 2>,
+}
+
+fn upload() -> Upload {
+    Upload {
+        body: b"ABC".to_vec(),
+        nonce: BoundedByteBuf::try_from(b"AB".to_vec()).unwrap(),
+    }
+}
+
+#[test]
+fn test_bounded() {
+    assert_tokens(
+        &upload().readable(),
+        &[
+            Token::Struct {
+                name: "Upload",
+                len: 2,
+            },
+            Token::Str("body"),
+            Token::Str("414243"),
+            Token::Str("nonce"),
+            Token::Str("4142"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &upload().compact(),
+        &[
+            Token::Struct {
+                name: "Upload",
+                len: 2,
+            },
+            Token::Str("body"),
+            Token::ByteBuf(b"ABC"),
+            Token::Str("nonce"),
+            Token::ByteBuf(b"AB"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_bounded_rejects() {
+    let body = |value| {
+        [
+            Token::Struct {
+                name: "Upload",
+                len: 2,
+            },
+            Token::Str("body"),
+            value,
+        ]
+    };
+    assert_de_tokens_error::<Readable<Upload>>(
+        &body(Token::Str("4142434445")),
+        "invalid length 5, expected a hex string of 1 to 3 bytes",
+    );
+    assert_de_tokens_error::<Readable<Upload>>(
+        &body(Token::Str("")),
+        "invalid length 0, expected a hex string of 1 to 3 bytes",
+    );
+    assert_de_tokens_error::<Compact<Upload>>(
+        &body(Token::Bytes(b"ABCD")),
+        "invalid length 4, expected a byte array of 1 to 3 bytes",
+    );
+
+    let mut seq = vec![
+        Token::Struct {
+            name: "Upload",
+            len: 2,
+        },
+        Token::Str("body"),
+        Token::Seq { len: None },
+    ];
+    // Rejected at the fourth element, before reading any further.
+    seq.extend(vec![Token::U8(0); 4]);
+    assert_de_tokens_error::<Compact<Upload>>(
+        &seq,
+        "invalid length 4, expected a byte array of 1 to 3 bytes",
+    );
+}
+
+#[test]
+fn test_bounded_bincode_limit() {
+    use bincode::Options;
+
+    // A body length prefix of 2^40 bytes, with no data behind it.
+    let mut input = (1u64 << 40).to_le_bytes().to_vec();
+    input.extend_from_slice(b"AB");
+
+    let err = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_limit(4096)
+        .deserialize_from::<_, Upload>(&input[..])
+        .unwrap_err();
+    assert!(matches!(*err, bincode::ErrorKind::SizeLimit));
+}
@@ -0,0 +1,140 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Device {
+    #[serde(with = "serde_human_bytes::checked")]
+    id: [u8; 3],
+
+    #[serde(with = "serde_human_bytes::checked::Grouped::<4>")]
+    serial: Vec<u8>,
+
+    #[serde(with = "serde_human_bytes::checked::crockford")]
+    code: Vec<u8>,
+
+    #[serde(with = "serde_human_bytes::checked::crockford::Grouped::<5>")]
+    recovery: Option<[u8; 10]>,
+}
+
+fn device() -> Device {
+    Device {
+        id: *b"ABC",
+        serial: b"ABC".to_vec(),
+        code: b"ABC".to_vec(),
+        recovery: Some(*b"0123456789"),
+    }
+}
+
+#[test]
+fn test_checked_tokens() {
+    assert_tokens(
+        &device().readable(),
+        &[
+            Token::Struct {
+                name: "Device",
+                len: 4,
+            },
+            Token::Str("id"),
+            Token::Str("414243a3830348"),
+            Token::Str("serial"),
+            Token::Str("4142-43a3-8303-48"),
+            Token::Str("code"),
+            Token::Str("851478W30D40"),
+            Token::Str("recovery"),
+            Token::Some,
+            Token::Str("60RK4-CSM6M-V3EE1-SMT2C-FHG"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &device().compact(),
+        &[
+            Token::Struct {
+                name: "Device",
+                len: 4,
+            },
+            Token::Str("id"),
+            Token::Bytes(b"ABC"),
+            Token::Str("serial"),
+            Token::Bytes(b"ABC"),
+            Token::Str("code"),
+            Token::Bytes(b"ABC"),
+            Token::Str("recovery"),
+            Token::Some,
+            Token::Bytes(b"0123456789"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_checked_lenient_input() {
+    assert_de_tokens(
+        &device().readable(),
+        &[
+            Token::Struct {
+                name: "Device",
+                len: 4,
+            },
+            Token::Str("id"),
+            Token::Str("414-243-A38-303-48"),
+            Token::Str("serial"),
+            Token::Str("414243a3830348"),
+            Token::Str("code"),
+            Token::Str("85147-8w3o-d4O"),
+            Token::Str("recovery"),
+            Token::Some,
+            Token::Str("60rk4csm6mv3ee1smt2cfhg"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Id(#[serde(with = "serde_human_bytes::checked")] [u8; 3]);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Code(#[serde(with = "serde_human_bytes::checked::crockford")] Vec<u8>);
+
+#[test]
+fn test_checked_errors() {
+    assert_de_tokens_error::<serde_test::Readable<Id>>(
+        &[
+            Token::NewtypeStruct { name: "Id" },
+            Token::Str("414243a3830349"),
+        ],
+        "invalid value: checksum mismatch, expected a checksummed hex string of 3 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Id>>(
+        &[
+            Token::NewtypeStruct { name: "Id" },
+            Token::Str("41424xa3830348"),
+        ],
+        "invalid value: invalid character 'x' at offset 5, expected a checksummed hex string of 3 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Id>>(
+        &[
+            Token::NewtypeStruct { name: "Id" },
+            Token::Str("414230694c07"),
+        ],
+        "invalid length 2, expected a checksummed hex string of 3 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Code>>(
+        &[
+            Token::NewtypeStruct { name: "Code" },
+            Token::Str("851478W30D41"),
+        ],
+        "invalid value: invalid padding at offset 11, expected a checksummed Crockford base32 string",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Code>>(
+        &[
+            Token::NewtypeStruct { name: "Code" },
+            Token::Str("851478W30D60"),
+        ],
+        "invalid value: checksum mismatch, expected a checksummed Crockford base32 string",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Code>>(
+        &[Token::NewtypeStruct { name: "Code" }, Token::Str("8514")],
+        "invalid length 2, expected a checksummed Crockford base32 string",
+    );
+}
@@ -0,0 +1,143 @@
+#![cfg(all(feature = "smallvec", feature = "arrayvec", feature = "heapless"))]
+
+use arrayvec::ArrayVec;
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::IntoDeserializer;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+use smallvec::SmallVec;
+use std::convert::TryFrom;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Containers {
+    #[serde(with = "serde_human_bytes")]
+    small: SmallVec<[u8; 2]>,
+
+    #[serde(with = "serde_human_bytes")]
+    array_vec: ArrayVec<u8, 4>,
+
+    #[serde(with = "serde_human_bytes::base64")]
+    heapless: heapless::Vec<u8, 4>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Capped {
+    #[serde(with = "serde_human_bytes")]
+    bytes: heapless::Vec<u8, 2>,
+}
+
+fn containers() -> Containers {
+    Containers {
+        small: SmallVec::from_slice(b"ABC"),
+        array_vec: ArrayVec::try_from(&b"ABC"[..]).unwrap(),
+        heapless: heapless::Vec::from_slice(b"ABC").unwrap(),
+    }
+}
+
+#[test]
+fn test_containers_readable() {
+    assert_tokens(
+        &containers().readable(),
+        &[
+            Token::Struct {
+                name: "Containers",
+                len: 3,
+            },
+            Token::Str("small"),
+            Token::Str("414243"),
+            Token::Str("array_vec"),
+            Token::Str("414243"),
+            Token::Str("heapless"),
+            Token::Str("QUJD"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_containers_compact() {
+    assert_tokens(
+        &containers().compact(),
+        &[
+            Token::Struct {
+                name: "Containers",
+                len: 3,
+            },
+            Token::Str("small"),
+            Token::Bytes(b"ABC"),
+            Token::Str("array_vec"),
+            Token::Bytes(b"ABC"),
+            Token::Str("heapless"),
+            Token::Bytes(b"ABC"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_capacity_exceeded() {
+    let expected = "invalid length 3, expected a byte array of at most 2 bytes";
+    assert_de_tokens_error::<Readable<Capped>>(
+        &[
+            Token::Struct {
+                name: "Capped",
+                len: 1,
+            },
+            Token::Str("bytes"),
+            Token::Str("414243"),
+        ],
+        expected,
+    );
+    assert_de_tokens_error::<Compact<Capped>>(
+        &[
+            Token::Struct {
+                name: "Capped",
+                len: 1,
+            },
+            Token::Str("bytes"),
+            Token::Bytes(b"ABC"),
+        ],
+        expected,
+    );
+    assert_de_tokens_error::<Compact<Capped>>(
+        &[
+            Token::Struct {
+                name: "Capped",
+                len: 1,
+            },
+            Token::Str("bytes"),
+            Token::Seq { len: None },
+            Token::U8(65),
+            Token::U8(66),
+            Token::U8(67),
+        ],
+        expected,
+    );
+}
+
+#[test]
+fn test_base64_matches_decode() {
+    let alphabet = ['Q', 'U', '/', '=', '-'];
+    let mut inputs = vec![String::new()];
+    let mut start = 0;
+    for _ in 0..6 {
+        let end = inputs.len();
+        for i in start..end {
+            for c in &alphabet {
+                let longer = format!("{}{}", inputs[i], c);
+                inputs.push(longer);
+            }
+        }
+        start = end;
+    }
+
+    for input in inputs {
+        let de: StrDeserializer<Error> = input.as_str().into_deserializer();
+        let decoded: Result<heapless::Vec<u8, 8>, Error> =
+            serde_human_bytes::base64::deserialize(de);
+        match base64::decode(&input) {
+            Ok(expected) => assert_eq!(decoded.unwrap(), expected[..], "{:?}", input),
+            Err(_) => assert!(decoded.is_err(), "{:?}", input),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::IntoDeserializer;
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Key {
+    #[serde(with = "serde_human_bytes::ct::hex")]
+    private: [u8; 3],
+
+    #[serde(with = "serde_human_bytes::ct::base64")]
+    seed: Vec<u8>,
+}
+
+fn key() -> Key {
+    Key {
+        private: *b"ABC",
+        seed: b"ABCD".to_vec(),
+    }
+}
+
+#[test]
+fn test_ct_tokens() {
+    assert_tokens(
+        &key().readable(),
+        &[
+            Token::Struct {
+                name: "Key",
+                len: 2,
+            },
+            Token::Str("private"),
+            Token::Str("414243"),
+            Token::Str("seed"),
+            Token::Str("QUJDRA=="),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &key().compact(),
+        &[
+            Token::Struct {
+                name: "Key",
+                len: 2,
+            },
+            Token::Str("private"),
+            Token::Bytes(b"ABC"),
+            Token::Str("seed"),
+            Token::Bytes(b"ABCD"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+/// Every string of up to `max_len` characters drawn from `alphabet`.
+fn strings(alphabet: &[char], max_len: usize) -> Vec<String> {
+    let mut inputs = vec![String::new()];
+    let mut start = 0;
+    for _ in 0..max_len {
+        let end = inputs.len();
+        for i in start..end {
+            for c in alphabet {
+                let longer = format!("{}{}", inputs[i], c);
+                inputs.push(longer);
+            }
+        }
+        start = end;
+    }
+    inputs
+}
+
+fn de(input: &str) -> StrDeserializer<'_, Error> {
+    input.into_deserializer()
+}
+
+/// Bytes from a fixed-seed xorshift generator.
+fn random_bytes(state: &mut u32, len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            *state ^= *state << 13;
+            *state ^= *state >> 17;
+            *state ^= *state << 5;
+            state.to_le_bytes()[0]
+        })
+        .collect()
+}
+
+#[test]
+fn test_ct_hex_matches_regular() {
+    let alphabet: Vec<char> = (0u8..128).map(char::from).collect();
+    for input in strings(&alphabet, 2) {
+        let ct: Result<Vec<u8>, Error> = serde_human_bytes::ct::hex::deserialize(de(&input));
+        let regular: Result<Vec<u8>, Error> = serde_human_bytes::deserialize(de(&input));
+        assert_eq!(ct, regular, "{:?}", input);
+
+        let ct: Result<[u8; 1], Error> = serde_human_bytes::ct::hex::deserialize(de(&input));
+        let regular: Result<[u8; 1], Error> = serde_human_bytes::deserialize(de(&input));
+        assert_eq!(ct, regular, "{:?}", input);
+    }
+}
+
+#[test]
+fn test_ct_base64_matches_regular() {
+    let alphabet = ['Q', 'U', 'g', '+', '/', '=', '-', '9'];
+    for input in strings(&alphabet, 6) {
+        let ct: Result<Vec<u8>, Error> = serde_human_bytes::ct::base64::deserialize(de(&input));
+        let regular: Result<Vec<u8>, Error> = serde_human_bytes::base64::deserialize(de(&input));
+        match regular {
+            Ok(regular) => assert_eq!(ct.unwrap(), regular, "{:?}", input),
+            Err(_) => assert!(ct.is_err(), "{:?}", input),
+        }
+
+        let ct: Result<[u8; 3], Error> = serde_human_bytes::ct::base64::deserialize(de(&input));
+        let regular: Result<[u8; 3], Error> = serde_human_bytes::base64::deserialize(de(&input));
+        assert_eq!(ct, regular, "{:?}", input);
+    }
+}
+
+#[test]
+fn test_ct_round_trip_matches_regular() {
+    let mut state = 0x2545_f491;
+    for len in 0..200 {
+        let bytes = random_bytes(&mut state, len);
+
+        let hex: &'static str = Box::leak(::hex::encode(&bytes).into_boxed_str());
+        assert_tokens(
+            &Hex(bytes.clone()).readable(),
+            &[Token::NewtypeStruct { name: "Hex" }, Token::Str(hex)],
+        );
+
+        let base64: &'static str = Box::leak(::base64::encode(&bytes).into_boxed_str());
+        assert_tokens(
+            &Base64(bytes).readable(),
+            &[Token::NewtypeStruct { name: "Base64" }, Token::Str(base64)],
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Hex(#[serde(with = "serde_human_bytes::ct::hex")] Vec<u8>);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Base64(#[serde(with = "serde_human_bytes::ct::base64")] Vec<u8>);
@@ -0,0 +1,125 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::ByteArray;
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Readable, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Balance {
+    #[serde(with = "serde_human_bytes::decimal")]
+    amount: [u8; 16],
+
+    #[serde(with = "serde_human_bytes::decimal::le")]
+    fee: ByteArray<2>,
+
+    #[serde(with = "serde_human_bytes::decimal")]
+    total: Vec<u8>,
+}
+
+fn balance() -> Balance {
+    Balance {
+        amount: u128::MAX.to_be_bytes(),
+        fee: ByteArray::new(258u16.to_le_bytes()),
+        total: 12_345_678_901_234_567_890u64.to_be_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn test_decimal() {
+    assert_tokens(
+        &balance().readable(),
+        &[
+            Token::Struct {
+                name: "Balance",
+                len: 3,
+            },
+            Token::Str("amount"),
+            Token::Str("340282366920938463463374607431768211455"),
+            Token::Str("fee"),
+            Token::Str("258"),
+            Token::Str("total"),
+            Token::Str("12345678901234567890"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &balance().compact(),
+        &[
+            Token::Struct {
+                name: "Balance",
+                len: 3,
+            },
+            Token::Str("amount"),
+            Token::Bytes(&[0xff; 16]),
+            Token::Str("fee"),
+            Token::Bytes(&[2, 1]),
+            Token::Str("total"),
+            Token::ByteBuf(&[0xab, 0x54, 0xa9, 0x8c, 0xeb, 0x1f, 0x0a, 0xd2]),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_decimal_matches_u128() {
+    let mut n = 1u128;
+    while let Some(next) = n.checked_mul(7) {
+        n = next + 3;
+        let text: &'static str = Box::leak(n.to_string().into_boxed_str());
+        assert_tokens(
+            &Amount(n.to_be_bytes()).readable(),
+            &[Token::NewtypeStruct { name: "Amount" }, Token::Str(text)],
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Amount(#[serde(with = "serde_human_bytes::decimal")] [u8; 16]);
+
+#[test]
+fn test_decimal_invalid() {
+    let expected = "an unsigned decimal integer that fits in 16 bytes";
+    for input in &["340282366920938463463374607431768211456", "", "-1", "1e3"] {
+        assert_de_tokens_error::<Readable<Amount>>(
+            &[Token::NewtypeStruct { name: "Amount" }, Token::Str(input)],
+            &format!("invalid value: string {:?}, expected {}", input, expected),
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Large {
+    #[serde(with = "serde_human_bytes::decimal")]
+    array: [u8; 80],
+
+    #[serde(with = "serde_human_bytes::decimal")]
+    buf: Vec<u8>,
+}
+
+#[test]
+fn test_decimal_many_limbs() {
+    let mut array = [0u8; 80];
+    let mut b = 11u8;
+    for slot in &mut array {
+        *slot = b;
+        b = b.wrapping_add(37);
+    }
+    let mut buf = vec![0u8; 200];
+    let mut b = 7u8;
+    for slot in &mut buf {
+        *slot = b;
+        b = b.wrapping_add(101);
+    }
+    assert_tokens(
+        &Large { array, buf }.readable(),
+        &[
+            Token::Struct {
+                name: "Large",
+                len: 2,
+            },
+            Token::Str("array"),
+            Token::Str("199407246986271044645469988671258605397568696935458326430694308555649502782212989730051854063193298921798344117900525629047112229665361525933442734550276642533852528012612571106566670174146934"),
+            Token::Str("buf"),
+            Token::Str("1289595455137067654801556359097190170421167749378332449020799712743303275824192451717484323846731297240543400264411877160832138655834406120345930500948736460807715992359585249681173039202976264669123135325747987456225903641795112402682399939596790543380130181106563131760085434346464668635023644724934676167794956837726454107563545584799268699043716115430392069763233519377780554204239513314623249002415425789701301642866542107393176488153772635910106948593165486123384061787383178"),
+            Token::StructEnd,
+        ],
+    );
+}
@@ -2,8 +2,10 @@
 
 use serde_derive::{Deserialize, Serialize};
 use serde_human_bytes::{ByteArray, ByteBuf, Bytes};
-use serde_test::{assert_ser_tokens, assert_tokens, Configure, Token};
+use serde_test::{assert_de_tokens_error, assert_ser_tokens, assert_tokens, Configure, Token};
 use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 struct Test<'a> {
@@ -288,3 +290,117 @@ fn test_readable_owned() {
         ],
     );
 }
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct TestShared {
+    #[serde(with = "serde_human_bytes")]
+    rc_slice: Rc<[u8]>,
+
+    #[serde(with = "serde_human_bytes")]
+    arc_slice: Arc<[u8]>,
+
+    #[serde(with = "serde_human_bytes")]
+    arc_bytes: Arc<Bytes>,
+
+    #[serde(with = "serde_human_bytes")]
+    arc_array: Arc<[u8; 3]>,
+
+    #[serde(with = "serde_human_bytes::base64")]
+    boxed_array: Box<[u8; 3]>,
+}
+
+fn shared() -> TestShared {
+    TestShared {
+        rc_slice: Rc::from(&b"..."[..]),
+        arc_slice: Arc::from(&b"..."[..]),
+        arc_bytes: Arc::from(ByteBuf::from(b"...".as_ref()).into_boxed_bytes()),
+        arc_array: Arc::new(*b"..."),
+        boxed_array: Box::new(*b"..."),
+    }
+}
+
+#[test]
+fn test_shared() {
+    assert_tokens(
+        &shared().readable(),
+        &[
+            Token::Struct {
+                name: "TestShared",
+                len: 5,
+            },
+            Token::Str("rc_slice"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_slice"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_bytes"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_array"),
+            Token::Str("2e2e2e"),
+            Token::Str("boxed_array"),
+            Token::Str("Li4u"),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &shared().compact(),
+        &[
+            Token::Struct {
+                name: "TestShared",
+                len: 5,
+            },
+            Token::Str("rc_slice"),
+            Token::Bytes(b"..."),
+            Token::Str("arc_slice"),
+            Token::Bytes(b"..."),
+            Token::Str("arc_bytes"),
+            Token::Bytes(b"..."),
+            Token::Str("arc_array"),
+            Token::Bytes(b"..."),
+            Token::Str("boxed_array"),
+            Token::Bytes(b"..."),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_shared_array_length() {
+    assert_de_tokens_error::<serde_test::Readable<TestShared>>(
+        &[
+            Token::Struct {
+                name: "TestShared",
+                len: 5,
+            },
+            Token::Str("rc_slice"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_slice"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_bytes"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_array"),
+            Token::Str("2e2e"),
+        ],
+        "invalid length 2, expected an array of 3 bytes",
+    );
+
+    assert_de_tokens_error::<serde_test::Readable<TestShared>>(
+        &[
+            Token::Struct {
+                name: "TestShared",
+                len: 5,
+            },
+            Token::Str("rc_slice"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_slice"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_bytes"),
+            Token::Str("2e2e2e"),
+            Token::Str("arc_array"),
+            Token::Str("2e2e2e"),
+            Token::Str("boxed_array"),
+            Token::Str("Li4uLg=="),
+        ],
+        "invalid length 4, expected an array of 3 bytes",
+    );
+}
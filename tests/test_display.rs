@@ -0,0 +1,94 @@
+use serde_human_bytes::{ByteArray, ByteBuf, Bytes, DecodeError};
+
+#[test]
+fn test_display() {
+    let bytes = Bytes::new(b"\x01\xab\xCD");
+    assert_eq!(bytes.to_string(), "01abcd");
+    assert_eq!(format!("{:x}", bytes), "01abcd");
+    assert_eq!(format!("{:X}", bytes), "01ABCD");
+    assert_eq!(format!("{:#x}", bytes), "0x01abcd");
+    assert_eq!(format!("{:#X}", bytes), "0x01ABCD");
+
+    let buf = ByteBuf::from(vec![0xde, 0xad]);
+    assert_eq!(buf.to_string(), "dead");
+    assert_eq!(format!("{:#x}", buf), "0xdead");
+
+    let array = ByteArray::new([0xbe, 0xef]);
+    assert_eq!(array.to_string(), "beef");
+    assert_eq!(format!("{:#X}", array), "0xBEEF");
+
+    let long = ByteBuf::from(vec![0x5a; 1000]);
+    assert_eq!(long.to_string(), "5a".repeat(1000));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("01abCD".parse::<ByteBuf>().unwrap(), [0x01, 0xab, 0xcd]);
+    assert_eq!("".parse::<ByteBuf>().unwrap(), []);
+    assert_eq!("abc".parse::<ByteBuf>(), Err(DecodeError::OddLength));
+    assert_eq!(
+        "zz".parse::<ByteBuf>(),
+        Err(DecodeError::InvalidCharacter { c: 'z', index: 0 })
+    );
+
+    assert_eq!("beef".parse::<ByteArray<2>>().unwrap(), [0xbe, 0xef]);
+    assert_eq!(
+        "beef".parse::<ByteArray<3>>(),
+        Err(DecodeError::InvalidLength {
+            expected: 3,
+            actual: 2,
+        })
+    );
+    assert_eq!("bee".parse::<ByteArray<2>>(), Err(DecodeError::OddLength));
+}
+
+#[test]
+fn test_debug() {
+    let buf = ByteBuf::from(vec![65, 66, 67]);
+    assert_eq!(format!("{:?}", buf), "[65, 66, 67]");
+    assert_eq!(format!("{:x?}", buf), "[41, 42, 43]");
+    assert_eq!(format!("{:?}", buf.hex_debug()), "0x414243");
+
+    let array = ByteArray::new([0xbe, 0xef]);
+    assert_eq!(format!("{:?}", array.hex_debug()), "0xbeef");
+    assert_eq!(format!("{:?}", Bytes::new(b"").hex_debug()), "0x");
+}
+
+#[test]
+fn test_encoded_text() {
+    let buf = ByteBuf::from_hex("414243").unwrap();
+    assert_eq!(buf, b"ABC");
+    assert_eq!(buf.to_hex(), "414243");
+    assert_eq!(buf.to_base64(), "QUJD");
+    assert_eq!(ByteBuf::from_base64("QUJD").unwrap(), b"ABC");
+    assert_eq!(
+        ByteBuf::from_base64("QUJD="),
+        Err(DecodeError::InvalidPadding { index: 5 })
+    );
+    assert_eq!(
+        ByteBuf::from_hex("41x2"),
+        Err(DecodeError::InvalidCharacter { c: 'x', index: 2 })
+    );
+
+    let array = ByteArray::<3>::from_base64("QUJD").unwrap();
+    assert_eq!(array, b"ABC");
+    assert_eq!(array.to_hex(), "414243");
+    assert_eq!(array.to_base64(), "QUJD");
+    assert_eq!(
+        ByteArray::<4>::from_base64("QUJD"),
+        Err(DecodeError::InvalidLength {
+            expected: 4,
+            actual: 3,
+        })
+    );
+    assert_eq!(
+        ByteArray::<3>::from_hex("41424"),
+        Err(DecodeError::OddLength)
+    );
+
+    let mut out = String::new();
+    array.encode_to(&mut out).unwrap();
+    out.push(' ');
+    buf.encode_base64_to(&mut out).unwrap();
+    assert_eq!(out, "414243 QUJD");
+}
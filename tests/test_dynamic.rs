@@ -0,0 +1,101 @@
+use serde::de::{value, DeserializeSeed, IntoDeserializer};
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::dynamic::{self, Encoded, Encoding, Seed};
+use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Blob {
+    #[serde(with = "serde_human_bytes::dynamic")]
+    data: Vec<u8>,
+
+    #[serde(with = "serde_human_bytes::dynamic")]
+    id: Option<[u8; 2]>,
+}
+
+fn blob() -> Blob {
+    Blob {
+        data: b"ABC".to_vec(),
+        id: Some(*b"AB"),
+    }
+}
+
+fn tokens(data: &'static str, id: &'static str) -> [Token; 7] {
+    [
+        Token::Struct {
+            name: "Blob",
+            len: 2,
+        },
+        Token::Str("data"),
+        Token::Str(data),
+        Token::Str("id"),
+        Token::Some,
+        Token::Str(id),
+        Token::StructEnd,
+    ]
+}
+
+#[test]
+fn test_dynamic_default_hex() {
+    assert_eq!(dynamic::encoding(), Encoding::Hex);
+    assert_tokens(&blob().readable(), &tokens("414243", "4142"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_dynamic_scoped() {
+    dynamic::with_encoding(Encoding::Base64, || {
+        assert_tokens(&blob().readable(), &tokens("QUJD", "QUI="));
+        dynamic::with_encoding(Encoding::Hex, || {
+            assert_tokens(&blob().readable(), &tokens("414243", "4142"));
+        });
+        assert_eq!(dynamic::encoding(), Encoding::Base64);
+    });
+    assert_eq!(dynamic::encoding(), Encoding::Hex);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_dynamic_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        dynamic::with_encoding(Encoding::Base64, || panic!("serializer failed"));
+    });
+    assert!(result.is_err());
+    assert_eq!(dynamic::encoding(), Encoding::Hex);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_dynamic_compact() {
+    dynamic::with_encoding(Encoding::Base64, || {
+        assert_tokens(
+            &blob().compact(),
+            &[
+                Token::Struct {
+                    name: "Blob",
+                    len: 2,
+                },
+                Token::Str("data"),
+                Token::Bytes(b"ABC"),
+                Token::Str("id"),
+                Token::Some,
+                Token::Bytes(b"AB"),
+                Token::StructEnd,
+            ],
+        );
+    });
+}
+
+#[test]
+fn test_dynamic_explicit() {
+    let bytes = b"ABC".to_vec();
+    assert_ser_tokens(
+        &Encoded::new(&bytes, Encoding::Base64).readable(),
+        &[Token::Str("QUJD")],
+    );
+
+    let de = IntoDeserializer::<value::Error>::into_deserializer("QUJD");
+    let value: Vec<u8> = Seed::new(Encoding::Base64).deserialize(de).unwrap();
+    assert_eq!(value, b"ABC");
+
+    assert_de_tokens(&blob().readable(), &tokens("414243", "4142"));
+}
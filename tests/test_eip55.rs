@@ -0,0 +1,117 @@
+#![cfg(feature = "eip55")]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::ByteArray;
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Address(#[serde(with = "serde_human_bytes::eip55")] [u8; 20]);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Transfer {
+    #[serde(with = "serde_human_bytes::eip55")]
+    to: ByteArray<20>,
+    #[serde(with = "serde_human_bytes::eip55")]
+    from: Option<[u8; 20]>,
+}
+
+fn address(hex: &str) -> [u8; 20] {
+    let mut bytes = [0; 20];
+    hex::decode_to_slice(hex, &mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_eip55_spec_vectors() {
+    for checksummed in &[
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        "0x52908400098527886E0F7030069857D2E4169EE7",
+        "0xde709f2102306220921060314715629080e2fb77",
+    ] {
+        let bytes = address(&checksummed[2..].to_lowercase());
+        assert_tokens(
+            &Address(bytes).readable(),
+            &[
+                Token::NewtypeStruct { name: "Address" },
+                Token::Str(checksummed),
+            ],
+        );
+    }
+}
+
+fn transfer() -> Transfer {
+    Transfer {
+        to: ByteArray::new(address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")),
+        from: None,
+    }
+}
+
+#[test]
+fn test_eip55_tokens() {
+    assert_tokens(
+        &transfer().readable(),
+        &[
+            Token::Struct {
+                name: "Transfer",
+                len: 2,
+            },
+            Token::Str("to"),
+            Token::Str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Token::Str("from"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &transfer().compact(),
+        &[
+            Token::Struct {
+                name: "Transfer",
+                len: 2,
+            },
+            Token::Str("to"),
+            Token::Bytes(
+                b"\x5a\xae\xb6\x05\x3f\x3e\x94\xc9\xb9\xa0\x9f\x33\x66\x94\x35\xe7\xef\x1b\xea\xed",
+            ),
+            Token::Str("from"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_eip55_uniform_case() {
+    let bytes = address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+    for input in &[
+        "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+        "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+        "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+    ] {
+        assert_de_tokens(
+            &Address(bytes).readable(),
+            &[Token::NewtypeStruct { name: "Address" }, Token::Str(input)],
+        );
+    }
+}
+
+#[test]
+fn test_eip55_errors() {
+    assert_de_tokens_error::<serde_test::Readable<Address>>(
+        &[
+            Token::NewtypeStruct { name: "Address" },
+            Token::Str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+        ],
+        "invalid value: checksum mismatch, expected an EIP-55 address",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Address>>(
+        &[
+            Token::NewtypeStruct { name: "Address" },
+            Token::Str("0x5aaeb6053f3e94c9"),
+        ],
+        "invalid length 8, expected an EIP-55 address",
+    );
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "derive")]
+#![allow(clippy::ref_option_ref)]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::{human_bytes, ByteBuf};
+use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Configure, Token};
+use std::borrow::Cow;
+
+#[human_bytes]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Test<'a> {
+    vec: Vec<u8>,
+    array: [u8; 2],
+    slice: &'a [u8],
+    boxed: Box<[u8]>,
+    cow: Cow<'a, [u8]>,
+    opt_slice: Option<&'a [u8]>,
+    opt_cow: Option<Cow<'a, [u8]>>,
+
+    #[human_bytes(skip)]
+    plain: Vec<u8>,
+
+    #[human_bytes(base64)]
+    buf: ByteBuf,
+
+    #[serde(with = "serde_human_bytes::padded")]
+    padded: [u8; 2],
+}
+
+#[test]
+fn test_struct() {
+    let test = Test {
+        vec: b"A".to_vec(),
+        array: *b"AB",
+        slice: b"C",
+        boxed: b"D".to_vec().into_boxed_slice(),
+        cow: Cow::Borrowed(b"E"),
+        opt_slice: Some(b"F"),
+        opt_cow: None,
+        plain: vec![1],
+        buf: ByteBuf::from(b"ABC".to_vec()),
+        padded: [0, 1],
+    };
+
+    assert_ser_tokens(
+        &(&test).readable(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 10,
+            },
+            Token::Str("vec"),
+            Token::Str("41"),
+            Token::Str("array"),
+            Token::Str("4142"),
+            Token::Str("slice"),
+            Token::BorrowedStr("43"),
+            Token::Str("boxed"),
+            Token::Str("44"),
+            Token::Str("cow"),
+            Token::BorrowedStr("45"),
+            Token::Str("opt_slice"),
+            Token::Some,
+            Token::BorrowedStr("46"),
+            Token::Str("opt_cow"),
+            Token::None,
+            Token::Str("plain"),
+            Token::Seq { len: Some(1) },
+            Token::U8(1),
+            Token::SeqEnd,
+            Token::Str("buf"),
+            Token::Str("QUJD"),
+            Token::Str("padded"),
+            Token::Str("01"),
+            Token::StructEnd,
+        ],
+    );
+
+    assert_tokens(
+        &test.compact(),
+        &[
+            Token::Struct {
+                name: "Test",
+                len: 10,
+            },
+            Token::Str("vec"),
+            Token::Bytes(b"A"),
+            Token::Str("array"),
+            Token::Bytes(b"AB"),
+            Token::Str("slice"),
+            Token::BorrowedBytes(b"C"),
+            Token::Str("boxed"),
+            Token::Bytes(b"D"),
+            Token::Str("cow"),
+            Token::BorrowedBytes(b"E"),
+            Token::Str("opt_slice"),
+            Token::Some,
+            Token::BorrowedBytes(b"F"),
+            Token::Str("opt_cow"),
+            Token::None,
+            Token::Str("plain"),
+            Token::Seq { len: Some(1) },
+            Token::U8(1),
+            Token::SeqEnd,
+            Token::Str("buf"),
+            Token::Bytes(b"ABC"),
+            Token::Str("padded"),
+            Token::Bytes(b"\x01"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[human_bytes(base64)]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Message {
+    Data(Vec<u8>, #[human_bytes(hex)] [u8; 1]),
+    Key { key: Option<Box<[u8]>> },
+}
+
+#[test]
+fn test_enum() {
+    assert_tokens(
+        &Message::Data(b"ABC".to_vec(), [0xff]).readable(),
+        &[
+            Token::TupleVariant {
+                name: "Message",
+                variant: "Data",
+                len: 2,
+            },
+            Token::Str("QUJD"),
+            Token::Str("ff"),
+            Token::TupleVariantEnd,
+        ],
+    );
+
+    assert_tokens(
+        &Message::Key {
+            key: Some(b"AB".to_vec().into_boxed_slice()),
+        }
+        .compact(),
+        &[
+            Token::StructVariant {
+                name: "Message",
+                variant: "Key",
+                len: 1,
+            },
+            Token::Str("key"),
+            Token::Some,
+            Token::Bytes(b"AB"),
+            Token::StructVariantEnd,
+        ],
+    );
+
+    assert_de_tokens(
+        &Message::Key { key: None }.readable(),
+        &[
+            Token::StructVariant {
+                name: "Message",
+                variant: "Key",
+                len: 1,
+            },
+            Token::Str("key"),
+            Token::None,
+            Token::StructVariantEnd,
+        ],
+    );
+}
+
+#[human_bytes]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Borrowed<'a> {
+    #[serde(borrow)]
+    payload: Cow<'a, [u8]>,
+
+    #[serde(borrow = "'a")]
+    opt: Option<Cow<'a, [u8]>>,
+}
+
+#[test]
+fn test_existing_borrow() {
+    let borrowed = Borrowed {
+        payload: Cow::Borrowed(b"A"),
+        opt: Some(Cow::Borrowed(b"B")),
+    };
+
+    assert_tokens(
+        &borrowed.compact(),
+        &[
+            Token::Struct {
+                name: "Borrowed",
+                len: 2,
+            },
+            Token::Str("payload"),
+            Token::BorrowedBytes(b"A"),
+            Token::Str("opt"),
+            Token::Some,
+            Token::BorrowedBytes(b"B"),
+            Token::StructEnd,
+        ],
+    );
+}
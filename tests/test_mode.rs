@@ -0,0 +1,95 @@
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::mode::{Compact, Readable};
+use serde_human_bytes::ByteBuf;
+use serde_test::{assert_tokens, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct AuditEntry {
+    #[serde(with = "serde_human_bytes::mode::readable")]
+    digest: [u8; 2],
+
+    #[serde(with = "serde_human_bytes::mode::compact")]
+    payload: Vec<u8>,
+
+    #[serde(with = "serde_human_bytes")]
+    tag: Option<Vec<u8>>,
+}
+
+fn entry() -> AuditEntry {
+    AuditEntry {
+        digest: *b"AB",
+        payload: b"CD".to_vec(),
+        tag: Some(b"E".to_vec()),
+    }
+}
+
+#[test]
+fn test_mode_fields() {
+    assert_tokens(
+        &entry().readable(),
+        &[
+            Token::Struct {
+                name: "AuditEntry",
+                len: 3,
+            },
+            Token::Str("digest"),
+            Token::Str("4142"),
+            Token::Str("payload"),
+            Token::Bytes(b"CD"),
+            Token::Str("tag"),
+            Token::Some,
+            Token::Str("45"),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &entry().compact(),
+        &[
+            Token::Struct {
+                name: "AuditEntry",
+                len: 3,
+            },
+            Token::Str("digest"),
+            Token::Str("4142"),
+            Token::Str("payload"),
+            Token::Bytes(b"CD"),
+            Token::Str("tag"),
+            Token::Some,
+            Token::Bytes(b"E"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_mode_readable_serializer() {
+    let value = vec![Some(ByteBuf::from(b"AB".to_vec()))];
+
+    let mut buf = Vec::new();
+    let mut serializer = bincode::Serializer::new(&mut buf, bincode::DefaultOptions::new());
+    value.serialize(Readable::new(&mut serializer)).unwrap();
+    assert_eq!(buf, b"\x01\x01\x044142");
+
+    let mut deserializer = bincode::Deserializer::from_slice(&buf, bincode::DefaultOptions::new());
+    let back = Vec::<Option<ByteBuf>>::deserialize(Readable::new(&mut deserializer)).unwrap();
+    assert_eq!(back, value);
+
+    let plain = bincode::DefaultOptions::new().serialize(&value).unwrap();
+    assert_eq!(plain, b"\x01\x01\x02AB");
+}
+
+#[test]
+fn test_mode_compact_value() {
+    let value = (ByteBuf::from(b"AB".to_vec()), ByteBuf::from(b"C".to_vec()));
+    assert_tokens(
+        &Compact::new(value).readable(),
+        &[
+            Token::Tuple { len: 2 },
+            Token::Bytes(b"AB"),
+            Token::Bytes(b"C"),
+            Token::TupleEnd,
+        ],
+    );
+}
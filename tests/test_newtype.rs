@@ -0,0 +1,74 @@
+use serde_human_bytes::{declare_byte_newtype, DecodeError};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+const KEY_LEN: usize = 2;
+
+declare_byte_newtype! {
+    /// A short hash.
+    pub struct Hash([u8; 3]) as Hex, prefix = "0x";
+
+    struct Key([u8; 2 * KEY_LEN]) as Base64;
+
+    struct Tag([u8; 2]);
+}
+
+#[test]
+fn test_text() {
+    let hash = Hash::new(*b"ABC");
+    assert_eq!(hash.to_string(), "0x414243");
+    assert_eq!(format!("{:?}", hash), "Hash(414243)");
+    assert_eq!("0x414243".parse::<Hash>(), Ok(hash));
+    assert_eq!(hash.as_ref(), b"ABC");
+    assert_eq!(<[u8; 3]>::from(hash), *b"ABC");
+
+    assert_eq!(
+        "414243".parse::<Hash>(),
+        Err(DecodeError::MissingPrefix { prefix: "0x" })
+    );
+    assert_eq!(
+        "0x4142".parse::<Hash>(),
+        Err(DecodeError::InvalidLength {
+            expected: 3,
+            actual: 2,
+        })
+    );
+    assert_eq!(
+        "0x41x2".parse::<Hash>(),
+        Err(DecodeError::InvalidLength {
+            expected: 3,
+            actual: 2,
+        })
+    );
+    assert_eq!(
+        "0x41x243".parse::<Hash>(),
+        Err(DecodeError::InvalidCharacter { c: 'x', index: 4 })
+    );
+
+    let key = Key::from(*b"ABCD");
+    assert_eq!(key.to_string(), "QUJDRA==");
+    assert_eq!(format!("{:?}", key), "Key(41424344)");
+    assert_eq!("QUJDRA==".parse::<Key>(), Ok(key));
+
+    assert_eq!(Tag::default().to_string(), "0000");
+    assert!(Tag::new([0, 1]) < Tag::new([1, 0]));
+}
+
+#[test]
+fn test_serde() {
+    assert_tokens(&Hash::new(*b"ABC").readable(), &[Token::Str("0x414243")]);
+    assert_tokens(&Hash::new(*b"ABC").compact(), &[Token::Bytes(b"ABC")]);
+    assert_tokens(&Key::new(*b"ABCD").readable(), &[Token::Str("QUJDRA==")]);
+    assert_tokens(&Tag::new(*b"AB").readable(), &[Token::Str("4142")]);
+}
+
+#[test]
+fn test_serde_error() {
+    assert_de_tokens_error::<serde_test::Readable<Hash>>(
+        &[Token::Str("414243")],
+        "invalid value: missing \"0x\" prefix, expected a hex string of 3 bytes prefixed with \"0x\"",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Key>>(
+        &[Token::Str("QUJD")],
+        "invalid length 3, expected a base64 string of 4 bytes",
+    );
+}
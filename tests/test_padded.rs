@@ -0,0 +1,91 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::ByteArray;
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Account {
+    #[serde(with = "serde_human_bytes::padded")]
+    balance: [u8; 4],
+
+    #[serde(with = "serde_human_bytes::quantity")]
+    nonce: ByteArray<4>,
+
+    #[serde(with = "serde_human_bytes::quantity")]
+    zero: [u8; 2],
+}
+
+fn account() -> Account {
+    Account {
+        balance: [0, 0, 1, 0],
+        nonce: ByteArray::new([0, 0, 1, 0]),
+        zero: [0, 0],
+    }
+}
+
+fn tokens(balance: &'static str, nonce: &'static str, zero: &'static str) -> [Token; 8] {
+    [
+        Token::Struct {
+            name: "Account",
+            len: 3,
+        },
+        Token::Str("balance"),
+        Token::Str(balance),
+        Token::Str("nonce"),
+        Token::Str(nonce),
+        Token::Str("zero"),
+        Token::Str(zero),
+        Token::StructEnd,
+    ]
+}
+
+#[test]
+fn test_padded_readable() {
+    assert_tokens(&account().readable(), &tokens("0100", "0x100", "0x0"));
+    assert_de_tokens(&account().readable(), &tokens("00000100", "0x0100", "0x00"));
+}
+
+#[test]
+fn test_padded_compact() {
+    assert_tokens(
+        &account().compact(),
+        &[
+            Token::Struct {
+                name: "Account",
+                len: 3,
+            },
+            Token::Str("balance"),
+            Token::Bytes(&[1, 0]),
+            Token::Str("nonce"),
+            Token::Bytes(&[1, 0]),
+            Token::Str("zero"),
+            Token::Bytes(&[]),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_padded_invalid() {
+    assert_de_tokens_error::<Readable<Account>>(
+        &tokens("0102030405", "0x1", "0x0")[..3],
+        "invalid length 5, expected a hex string of at most 4 bytes",
+    );
+    assert_de_tokens_error::<Readable<Account>>(
+        &tokens("100", "0x1", "0x0")[..3],
+        "invalid value: odd number of hex digits, expected a hex string of at most 4 bytes",
+    );
+    assert_de_tokens_error::<Readable<Account>>(
+        &tokens("01", "100", "0x0")[..5],
+        "invalid value: string \"100\", expected a 0x-prefixed hex quantity of at most 4 bytes",
+    );
+    assert_de_tokens_error::<Readable<Account>>(
+        &tokens("01", "0x", "0x0")[..5],
+        "invalid value: string \"0x\", expected a 0x-prefixed hex quantity of at most 4 bytes",
+    );
+    assert_de_tokens_error::<Readable<Account>>(
+        &tokens("01", "0x1", "0x12345")[..7],
+        "invalid length 3, expected a 0x-prefixed hex quantity of at most 2 bytes",
+    );
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "std")]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::ByteArray;
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Readable, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Host {
+    #[serde(with = "serde_human_bytes::uuid")]
+    id: [u8; 16],
+
+    #[serde(with = "serde_human_bytes::ip")]
+    v4: [u8; 4],
+
+    #[serde(with = "serde_human_bytes::ip")]
+    v6: ByteArray<16>,
+
+    #[serde(with = "serde_human_bytes::mac")]
+    mac: [u8; 6],
+}
+
+const ID: [u8; 16] = [
+    0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00, 0x00,
+];
+
+fn host() -> Host {
+    let mut v6 = [0; 16];
+    v6[15] = 1;
+    Host {
+        id: ID,
+        v4: [10, 0, 0, 1],
+        v6: ByteArray::new(v6),
+        mac: [0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+    }
+}
+
+fn tokens(id: &'static str, v6: &'static str, mac: &'static str) -> [Token; 10] {
+    [
+        Token::Struct {
+            name: "Host",
+            len: 4,
+        },
+        Token::Str("id"),
+        Token::Str(id),
+        Token::Str("v4"),
+        Token::Str("10.0.0.1"),
+        Token::Str("v6"),
+        Token::Str(v6),
+        Token::Str("mac"),
+        Token::Str(mac),
+        Token::StructEnd,
+    ]
+}
+
+#[test]
+fn test_presentation_readable() {
+    assert_tokens(
+        &host().readable(),
+        &tokens(
+            "550e8400-e29b-41d4-a716-446655440000",
+            "::1",
+            "aa:bb:cc:dd:ee:ff",
+        ),
+    );
+    assert_de_tokens(
+        &host().readable(),
+        &tokens(
+            "{550E8400-E29B-41D4-A716-446655440000}",
+            "0:0:0:0:0:0:0:1",
+            "AA-BB-CC-DD-EE-FF",
+        ),
+    );
+    assert_de_tokens(
+        &host().readable(),
+        &tokens(
+            "urn:uuid:550e8400e29b41d4a716446655440000",
+            "::0.0.0.1",
+            "aabb.ccdd.eeff",
+        ),
+    );
+}
+
+#[test]
+fn test_presentation_compact() {
+    assert_tokens(
+        &host().compact(),
+        &[
+            Token::Struct {
+                name: "Host",
+                len: 4,
+            },
+            Token::Str("id"),
+            Token::Bytes(&ID),
+            Token::Str("v4"),
+            Token::Bytes(&[10, 0, 0, 1]),
+            Token::Str("v6"),
+            Token::Bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            Token::Str("mac"),
+            Token::Bytes(&[0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_presentation_invalid() {
+    assert_de_tokens_error::<Readable<Host>>(
+        &tokens("550e8400-e29b-41d4-a716_446655440000", "::1", "")[..3],
+        "invalid value: string \"550e8400-e29b-41d4-a716_446655440000\", expected a UUID",
+    );
+    let mut v4 = tokens("550e8400-e29b-41d4-a716-446655440000", "::1", "");
+    v4[4] = Token::Str("10.0.0.256");
+    assert_de_tokens_error::<Readable<Host>>(
+        &v4[..5],
+        "invalid value: string \"10.0.0.256\", expected an IPv4 address",
+    );
+    assert_de_tokens_error::<Readable<Host>>(
+        &tokens(
+            "550e8400-e29b-41d4-a716-446655440000",
+            "::1",
+            "aa:bb-cc:dd:ee:ff",
+        )[..9],
+        "invalid value: string \"aa:bb-cc:dd:ee:ff\", expected a MAC address",
+    );
+}
@@ -0,0 +1,96 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{
+    assert_de_tokens_error, assert_ser_tokens, assert_tokens, Configure, Readable, Token,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Request {
+    #[serde(with = "serde_human_bytes::redact")]
+    payload: Vec<u8>,
+
+    #[serde(with = "serde_human_bytes::redact::Preview::<2, 1>")]
+    session: [u8; 4],
+
+    #[serde(with = "serde_human_bytes::redact::Preview::<2, 1>")]
+    short: Option<Vec<u8>>,
+}
+
+fn request() -> Request {
+    Request {
+        payload: b"A".to_vec(),
+        session: *b"ABCD",
+        short: Some(b"ABC".to_vec()),
+    }
+}
+
+#[test]
+fn test_redact_readable() {
+    assert_ser_tokens(
+        &request().readable(),
+        &[
+            Token::Struct {
+                name: "Request",
+                len: 3,
+            },
+            Token::Str("payload"),
+            Token::Str("<1 byte>"),
+            Token::Str("session"),
+            Token::Str("<4 bytes 4142…44>"),
+            Token::Str("short"),
+            Token::Some,
+            Token::Str("<3 bytes>"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_redact_compact() {
+    assert_tokens(
+        &request().compact(),
+        &[
+            Token::Struct {
+                name: "Request",
+                len: 3,
+            },
+            Token::Str("payload"),
+            Token::Bytes(b"A"),
+            Token::Str("session"),
+            Token::Bytes(b"ABCD"),
+            Token::Str("short"),
+            Token::Some,
+            Token::Bytes(b"ABC"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_redact_rejects_readable() {
+    assert_de_tokens_error::<Readable<Request>>(
+        &[
+            Token::Struct {
+                name: "Request",
+                len: 3,
+            },
+            Token::Str("payload"),
+            Token::Str("<1 byte>"),
+        ],
+        "redacted bytes cannot be deserialized",
+    );
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn test_redact_sha256() {
+    #[derive(Serialize)]
+    struct Digest(#[serde(with = "serde_human_bytes::redact::sha256")] Vec<u8>);
+
+    assert_ser_tokens(
+        &Digest(b"abc".to_vec()).readable(),
+        &[
+            Token::NewtypeStruct { name: "Digest" },
+            Token::Str("<3 bytes sha256:ba7816bf…>"),
+        ],
+    );
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "secret")]
+
+use serde_derive::{Deserialize, Serialize};
+use serde_human_bytes::{SecretArray, SecretBytes};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Readable, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Keys {
+    #[serde(with = "serde_human_bytes")]
+    session: SecretBytes,
+
+    #[serde(with = "serde_human_bytes::base64")]
+    signing: SecretArray<3>,
+
+    plain: SecretArray<2>,
+}
+
+fn keys() -> Keys {
+    Keys {
+        session: SecretBytes::from(b"ABC".to_vec()),
+        signing: SecretArray::new(*b"ABC"),
+        plain: SecretArray::new(*b"AB"),
+    }
+}
+
+#[test]
+fn test_secret_readable() {
+    assert_tokens(
+        &keys().readable(),
+        &[
+            Token::Struct {
+                name: "Keys",
+                len: 3,
+            },
+            Token::Str("session"),
+            Token::Str("414243"),
+            Token::Str("signing"),
+            Token::Str("QUJD"),
+            Token::Str("plain"),
+            Token::Str("4142"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_secret_compact() {
+    assert_tokens(
+        &keys().compact(),
+        &[
+            Token::Struct {
+                name: "Keys",
+                len: 3,
+            },
+            Token::Str("session"),
+            Token::Bytes(b"ABC"),
+            Token::Str("signing"),
+            Token::Bytes(b"ABC"),
+            Token::Str("plain"),
+            Token::Bytes(b"AB"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_secret_invalid() {
+    assert_de_tokens_error::<Readable<SecretArray<2>>>(
+        &[Token::Str("414243")],
+        "invalid length 3, expected a hex string of 2 bytes",
+    );
+    assert_de_tokens_error::<Readable<SecretBytes>>(
+        &[Token::Str("41424")],
+        "invalid value: odd number of hex digits, expected a hex string",
+    );
+}
+
+#[test]
+fn test_secret_redacted() {
+    let keys = keys();
+    assert_eq!(format!("{:?}", keys.session), "[REDACTED; 3 bytes]");
+    assert_eq!(keys.signing.to_string(), "[REDACTED; 3 bytes]");
+    assert_eq!(keys.session.expose_secret(), b"ABC");
+    assert_ne!(keys.session, SecretBytes::from(b"ABD".to_vec()));
+    assert_ne!(keys.session, SecretBytes::from(b"AB".to_vec()));
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct CtKeys {
+    #[serde(with = "serde_human_bytes::ct::hex")]
+    session: SecretBytes,
+
+    #[serde(with = "serde_human_bytes::ct::base64")]
+    signing: SecretArray<3>,
+}
+
+#[test]
+fn test_secret_ct() {
+    let keys = CtKeys {
+        session: SecretBytes::from(b"ABC".to_vec()),
+        signing: SecretArray::new(*b"ABC"),
+    };
+    assert_tokens(
+        &keys.readable(),
+        &[
+            Token::Struct {
+                name: "CtKeys",
+                len: 2,
+            },
+            Token::Str("session"),
+            Token::Str("414243"),
+            Token::Str("signing"),
+            Token::Str("QUJD"),
+            Token::StructEnd,
+        ],
+    );
+}
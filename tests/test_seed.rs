@@ -0,0 +1,96 @@
+use bincode::Options;
+use serde::de::value::{self, StrDeserializer};
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+use serde_human_bytes::dynamic::Encoding;
+use serde_human_bytes::seed::{IntoBuf, IntoSlice};
+use serde_human_bytes::{ByteArray, ByteBuf};
+
+fn readable(s: &str) -> StrDeserializer<'_, value::Error> {
+    StrDeserializer::new(s)
+}
+
+#[test]
+fn test_into_buf() {
+    let mut buf = Vec::with_capacity(16);
+    let ptr = buf.as_ptr();
+
+    IntoBuf::new(&mut buf, Encoding::Hex)
+        .deserialize(readable("414243"))
+        .unwrap();
+    assert_eq!(buf, b"ABC");
+
+    IntoBuf::new(&mut buf, Encoding::Base64)
+        .deserialize(readable("QUI="))
+        .unwrap();
+    assert_eq!(buf, b"AB");
+
+    let bytes = bincode::DefaultOptions::new()
+        .serialize(&ByteBuf::from(b"XYZW".to_vec()))
+        .unwrap();
+    let mut deserializer =
+        bincode::Deserializer::from_slice(&bytes, bincode::DefaultOptions::new());
+    IntoBuf::new(&mut buf, Encoding::Hex)
+        .deserialize(&mut deserializer)
+        .unwrap();
+    assert_eq!(buf, b"XYZW");
+    assert_eq!(buf.as_ptr(), ptr);
+
+    let err = IntoBuf::new(&mut buf, Encoding::Hex)
+        .deserialize(readable("41x2"))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: invalid character 'x' at offset 2, expected a hex string"
+    );
+}
+
+#[test]
+fn test_into_slice() {
+    let mut slice = [0; 4];
+
+    let len = IntoSlice::new(&mut slice, Encoding::Hex)
+        .deserialize(readable("4142"))
+        .unwrap();
+    assert_eq!(&slice[..len], b"AB");
+
+    let len = IntoSlice::new(&mut slice, Encoding::Base64)
+        .deserialize(readable("QUJD"))
+        .unwrap();
+    assert_eq!(&slice[..len], b"ABC");
+
+    let err = IntoSlice::new(&mut slice, Encoding::Base64)
+        .deserialize(readable("QUJDREVG"))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 6, expected a base64 string of at most 4 bytes"
+    );
+}
+
+#[test]
+fn test_deserialize_in_place() {
+    let mut buf = ByteBuf::with_capacity(16);
+    let ptr = buf.as_ptr();
+    ByteBuf::deserialize_in_place(readable("414243"), &mut buf).unwrap();
+    assert_eq!(buf, b"ABC");
+    assert_eq!(buf.as_ptr(), ptr);
+
+    let mut array = ByteArray::new([0; 2]);
+    ByteArray::deserialize_in_place(readable("4142"), &mut array).unwrap();
+    assert_eq!(array, *b"AB");
+
+    let bytes = bincode::DefaultOptions::new()
+        .serialize(&ByteArray::new(*b"CD"))
+        .unwrap();
+    let mut deserializer =
+        bincode::Deserializer::from_slice(&bytes, bincode::DefaultOptions::new());
+    ByteArray::deserialize_in_place(&mut deserializer, &mut array).unwrap();
+    assert_eq!(array, *b"CD");
+
+    let err = ByteArray::deserialize_in_place(readable("41"), &mut array).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 1, expected a hex string of 2 bytes"
+    );
+}
@@ -1,6 +1,9 @@
 use serde_derive::{Deserialize, Serialize};
-use serde_human_bytes::{ByteArray, ByteBuf, Bytes};
-use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Configure, Token};
+use serde_human_bytes::{ByteArray, ByteBuf, Bytes, DecodeError};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, assert_tokens, Configure,
+    Readable, Token,
+};
 
 #[test]
 fn test_bytes_readable() {
@@ -204,3 +207,69 @@ fn test_base64_array_compact() {
         ],
     );
 }
+
+#[test]
+fn test_bytearray_readable_invalid() {
+    assert_de_tokens_error::<Readable<ByteArray<3>>>(
+        &[Token::Str("4142")],
+        "invalid length 2, expected a hex string of 3 bytes",
+    );
+    assert_de_tokens_error::<Readable<ByteArray<3>>>(
+        &[Token::Str("41424")],
+        "invalid value: odd number of hex digits, expected a hex string of 3 bytes",
+    );
+    assert_de_tokens_error::<Readable<ByteArray<3>>>(
+        &[Token::Str("41424x")],
+        "invalid value: invalid character 'x' at offset 5, expected a hex string of 3 bytes",
+    );
+}
+
+#[test]
+fn test_base64_readable_invalid() {
+    fn tokens(data: &'static str) -> [Token; 4] {
+        [
+            Token::Struct {
+                name: "Base64ArrayExample",
+                len: 1,
+            },
+            Token::Str("data"),
+            Token::Str(data),
+            Token::StructEnd,
+        ]
+    }
+    assert_de_tokens_error::<Readable<Base64ArrayExample>>(
+        &tokens("QUI="),
+        "invalid length 2, expected a base64 string of 3 bytes",
+    );
+    assert_de_tokens_error::<Readable<Base64ArrayExample>>(
+        &tokens("QU*D"),
+        "invalid value: invalid character '*' at offset 2, expected a base64 string of 3 bytes",
+    );
+    assert_de_tokens_error::<Readable<Base64ArrayExample>>(
+        &tokens("QUJDR"),
+        "invalid value: invalid padding at offset 5, expected a base64 string of 3 bytes",
+    );
+    assert_de_tokens_error::<Readable<Base64Example>>(
+        &[
+            Token::Struct {
+                name: "Base64Example",
+                len: 1,
+            },
+            Token::Str("data"),
+            Token::Str("QR=="),
+            Token::StructEnd,
+        ],
+        "invalid value: invalid padding at offset 1, expected a base64 string",
+    );
+}
+
+#[test]
+fn test_decode_error_display() {
+    let err = DecodeError::InvalidLength {
+        expected: 32,
+        actual: 31,
+    };
+    assert_eq!(err.to_string(), "invalid length 31, expected 32 bytes");
+    let err = DecodeError::InvalidCharacter { c: 'g', index: 7 };
+    assert_eq!(err.to_string(), "invalid character 'g' at offset 7");
+}
@@ -0,0 +1,121 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+use std::convert::TryFrom;
+
+#[derive(PartialEq, Debug)]
+struct PublicKey(Vec<u8>);
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for PublicKey {
+    type Error = String;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() == 2 {
+            Ok(PublicKey(bytes))
+        } else {
+            Err(format!(
+                "expected a 2-byte key, found {} bytes",
+                bytes.len()
+            ))
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Blob(Box<[u8]>);
+
+impl AsRef<[u8]> for Blob {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Blob {
+    fn from(bytes: Vec<u8>) -> Self {
+        Blob(bytes.into_boxed_slice())
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Nonce([u8; 3]);
+
+impl AsRef<[u8]> for Nonce {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; 3]> for Nonce {
+    fn from(bytes: [u8; 3]) -> Self {
+        Nonce(bytes)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Handshake {
+    #[serde(with = "serde_human_bytes::via")]
+    key: PublicKey,
+
+    #[serde(with = "serde_human_bytes::via")]
+    blob: Blob,
+
+    #[serde(with = "serde_human_bytes::via::Array::<3>")]
+    nonce: Nonce,
+}
+
+fn handshake() -> Handshake {
+    Handshake {
+        key: PublicKey(b"AB".to_vec()),
+        blob: Blob::from(b"C".to_vec()),
+        nonce: Nonce(*b"DEF"),
+    }
+}
+
+fn tokens(key: Token, blob: Token, nonce: Token) -> [Token; 8] {
+    [
+        Token::Struct {
+            name: "Handshake",
+            len: 3,
+        },
+        Token::Str("key"),
+        key,
+        Token::Str("blob"),
+        blob,
+        Token::Str("nonce"),
+        nonce,
+        Token::StructEnd,
+    ]
+}
+
+#[test]
+fn test_via() {
+    assert_tokens(
+        &handshake().readable(),
+        &tokens(Token::Str("4142"), Token::Str("43"), Token::Str("444546")),
+    );
+    assert_tokens(
+        &handshake().compact(),
+        &tokens(
+            Token::Bytes(b"AB"),
+            Token::Bytes(b"C"),
+            Token::Bytes(b"DEF"),
+        ),
+    );
+}
+
+#[test]
+fn test_via_errors() {
+    assert_de_tokens_error::<serde_test::Readable<Handshake>>(
+        &tokens(Token::Str("414243"), Token::Str("43"), Token::Str("444546"))[..3],
+        "expected a 2-byte key, found 3 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Handshake>>(
+        &tokens(Token::Str("4142"), Token::Str("43"), Token::Str("4445"))[..7],
+        "invalid length 2, expected a hex string of 3 bytes",
+    );
+}
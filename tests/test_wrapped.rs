@@ -0,0 +1,144 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Firmware {
+    #[serde(with = "serde_human_bytes::wrapped::Hex::<8>")]
+    header: [u8; 10],
+
+    #[serde(with = "serde_human_bytes::wrapped::Base64::<4>")]
+    image: Vec<u8>,
+}
+
+fn firmware() -> Firmware {
+    Firmware {
+        header: *b"ABCDEFGHIJ",
+        image: b"ABCDEFG".to_vec(),
+    }
+}
+
+#[test]
+fn test_wrapped_tokens() {
+    assert_tokens(
+        &firmware().readable(),
+        &[
+            Token::Struct {
+                name: "Firmware",
+                len: 2,
+            },
+            Token::Str("header"),
+            Token::Str("41424344\n45464748\n494a"),
+            Token::Str("image"),
+            Token::Str("QUJD\nREVG\nRw=="),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &firmware().compact(),
+        &[
+            Token::Struct {
+                name: "Firmware",
+                len: 2,
+            },
+            Token::Str("header"),
+            Token::Bytes(b"ABCDEFGHIJ"),
+            Token::Str("image"),
+            Token::Bytes(b"ABCDEFG"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Blobs {
+    #[serde(with = "serde_human_bytes::wrapped::hex")]
+    hex: Vec<u8>,
+
+    #[serde(with = "serde_human_bytes::wrapped::base64")]
+    base64: [u8; 7],
+
+    #[serde(with = "serde_human_bytes::wrapped::annotated")]
+    dump: Option<[u8; 4]>,
+}
+
+fn blobs() -> Blobs {
+    Blobs {
+        hex: b"ABCD".to_vec(),
+        base64: *b"ABCDEFG",
+        dump: Some(*b"ABCD"),
+    }
+}
+
+#[test]
+fn test_wrapped_single_line() {
+    assert_tokens(
+        &blobs().readable(),
+        &[
+            Token::Struct {
+                name: "Blobs",
+                len: 3,
+            },
+            Token::Str("hex"),
+            Token::Str("41424344"),
+            Token::Str("base64"),
+            Token::Str("QUJDREVGRw=="),
+            Token::Str("dump"),
+            Token::Some,
+            Token::Str("41424344"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_wrapped_skips_whitespace() {
+    assert_de_tokens(
+        &blobs().readable(),
+        &[
+            Token::Struct {
+                name: "Blobs",
+                len: 3,
+            },
+            Token::Str("hex"),
+            Token::Str("  4142\n  4344\n"),
+            Token::Str("base64"),
+            Token::Str("QUJD\r\n\tREVG\r\n\tRw==\r\n"),
+            Token::Str("dump"),
+            Token::Some,
+            Token::Str("# magic\n41 42  # AB\n43 44  # CD\n"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Hex(#[serde(with = "serde_human_bytes::wrapped::hex")] [u8; 2]);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Base64(#[serde(with = "serde_human_bytes::wrapped::base64")] Vec<u8>);
+
+#[test]
+fn test_wrapped_errors() {
+    assert_de_tokens_error::<serde_test::Readable<Hex>>(
+        &[Token::NewtypeStruct { name: "Hex" }, Token::Str("41\n4x")],
+        "invalid value: invalid character 'x' at offset 4, expected a hex string of 2 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Hex>>(
+        &[Token::NewtypeStruct { name: "Hex" }, Token::Str("41 # A")],
+        "invalid value: invalid character '#' at offset 3, expected a hex string of 2 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Hex>>(
+        &[
+            Token::NewtypeStruct { name: "Hex" },
+            Token::Str("41\n42\n43"),
+        ],
+        "invalid length 3, expected a hex string of 2 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<Base64>>(
+        &[
+            Token::NewtypeStruct { name: "Base64" },
+            Token::Str("QQ==\nQQ=="),
+        ],
+        "invalid value: invalid character 'Q' at offset 5, expected a base64 string",
+    );
+}